@@ -0,0 +1,79 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields};
+
+/// Generates `<field>_ptr` and `<field>_ptr_mut` accessors for every named field not marked `#[as_ptr(skip)]`.
+///
+/// `ptr::AsPtr` itself is already implemented for every type, so only the accessors are generated.
+#[proc_macro_derive(AsPtr, attributes(as_ptr))]
+pub fn derive_as_ptr(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+  let fields = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => &fields.named,
+      fields => {
+        return Err(Error::new_spanned(
+          fields,
+          "AsPtr can only be derived for structs with named fields",
+        ))
+      }
+    },
+    Data::Enum(data) => return Err(Error::new_spanned(data.enum_token, "AsPtr can only be derived for structs")),
+    Data::Union(data) => return Err(Error::new_spanned(data.union_token, "AsPtr can only be derived for structs")),
+  };
+
+  let mut accessors = Vec::new();
+
+  for field in fields {
+    if skipped(field)? {
+      continue;
+    }
+
+    let vis = &field.vis;
+    let ty = &field.ty;
+    let ident = field.ident.as_ref().expect("named fields always have an identifier");
+    let const_name = format_ident!("{}_ptr", ident);
+    let mut_name = format_ident!("{}_ptr_mut", ident);
+
+    accessors.push(quote! {
+      #vis fn #const_name(&self) -> ::ptr::ConstPtr<#ty> {
+        ::ptr::ConstPtr::new(&self.#ident)
+      }
+
+      #vis fn #mut_name(&mut self) -> ::ptr::MutPtr<#ty> {
+        ::ptr::MutPtr::new(&mut self.#ident)
+      }
+    });
+  }
+
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  Ok(quote! {
+    impl #impl_generics #name #ty_generics #where_clause {
+      #(#accessors)*
+    }
+  })
+}
+
+fn skipped(field: &Field) -> Result<bool, Error> {
+  let mut skip = false;
+
+  for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("as_ptr")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("skip") {
+        skip = true;
+        Ok(())
+      } else {
+        Err(meta.error("unsupported as_ptr attribute, expected `skip`"))
+      }
+    })?;
+  }
+
+  Ok(skip)
+}
@@ -0,0 +1,54 @@
+use ptr::prelude::*;
+
+#[derive(AsPtr)]
+struct Transform {
+  position: [f32; 3],
+  pub scale: f32,
+  #[as_ptr(skip)]
+  #[allow(dead_code)]
+  cache: Vec<u8>,
+}
+
+#[derive(AsPtr)]
+struct Wrapper<T: Clone>
+where
+  T: Default,
+{
+  inner: T,
+}
+
+#[test]
+fn accessors_point_at_fields() {
+  let mut transform = Transform {
+    position: [1.0, 2.0, 3.0],
+    scale: 1.0,
+    cache: Vec::new(),
+  };
+
+  assert_eq!(transform.position_ptr().raw(), &transform.position as *const [f32; 3]);
+  assert_eq!(transform.scale_ptr().raw(), &transform.scale as *const f32);
+
+  let mut scale = transform.scale_ptr_mut();
+  *scale = 2.0;
+  transform.position_ptr_mut()[1] = 5.0;
+
+  assert_eq!(transform.scale, 2.0);
+  assert_eq!(transform.position, [1.0, 5.0, 3.0]);
+}
+
+#[test]
+fn accessors_on_generic_structs() {
+  let mut wrapper = Wrapper {
+    inner: String::from("inner"),
+  };
+  assert_eq!(wrapper.inner_ptr().raw(), &wrapper.inner as *const String);
+
+  wrapper.inner_ptr_mut().push('!');
+  assert_eq!(wrapper.inner, "inner!");
+}
+
+#[test]
+fn ui() {
+  let cases = trybuild::TestCases::new();
+  cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use ptr::AsPtr;
+
+#[derive(AsPtr)]
+struct Node {
+  #[as_ptr(hide)]
+  value: u32,
+}
+
+fn main() {}
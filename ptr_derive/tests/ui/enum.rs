@@ -0,0 +1,9 @@
+use ptr::AsPtr;
+
+#[derive(AsPtr)]
+enum Shape {
+  Circle,
+  Square,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use ptr::AsPtr;
+
+#[derive(AsPtr)]
+struct Pair(u32, u32);
+
+fn main() {}
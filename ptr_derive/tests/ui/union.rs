@@ -0,0 +1,9 @@
+use ptr::AsPtr;
+
+#[derive(AsPtr)]
+union Bits {
+  int: u32,
+  float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,115 @@
+//! Pointer slots that several threads may read and replace at once
+//!
+//! Only the slot itself is synchronized. Dereferencing a loaded pointer is exactly as safe as dereferencing any other
+//! `ConstPtr` or `MutPtr`: the pointee has to outlive the read, and whoever mutates it has to pair the `Release` store
+//! that published it with an `Acquire` load on the reading side.
+
+use super::{ConstPtr, MutPtr};
+use core::{
+  fmt::{self, Debug, Formatter},
+  sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// An atomically replaceable `ConstPtr`
+pub struct AtomicConstPtr<T>(AtomicPtr<T>);
+
+impl<T> AtomicConstPtr<T> {
+  pub const fn new(ptr: ConstPtr<T>) -> Self {
+    Self(AtomicPtr::new(ptr.raw().cast_mut()))
+  }
+
+  pub fn load(&self, order: Ordering) -> ConstPtr<T> {
+    ConstPtr::from(self.0.load(order).cast_const())
+  }
+
+  pub fn store(&self, ptr: ConstPtr<T>, order: Ordering) {
+    self.0.store(ptr.raw().cast_mut(), order);
+  }
+
+  pub fn swap(&self, ptr: ConstPtr<T>, order: Ordering) -> ConstPtr<T> {
+    ConstPtr::from(self.0.swap(ptr.raw().cast_mut(), order).cast_const())
+  }
+
+  /// Stores `new` if the slot still holds `current`, either way returns what the slot held before
+  pub fn compare_exchange(
+    &self,
+    current: ConstPtr<T>,
+    new: ConstPtr<T>,
+    success: Ordering,
+    failure: Ordering,
+  ) -> Result<ConstPtr<T>, ConstPtr<T>> {
+    self
+      .0
+      .compare_exchange(current.raw().cast_mut(), new.raw().cast_mut(), success, failure)
+      .map(|ptr| ConstPtr::from(ptr.cast_const()))
+      .map_err(|ptr| ConstPtr::from(ptr.cast_const()))
+  }
+
+  pub fn into_inner(self) -> ConstPtr<T> {
+    ConstPtr::from(self.0.into_inner().cast_const())
+  }
+}
+
+impl<T> Default for AtomicConstPtr<T> {
+  fn default() -> Self {
+    Self::new(ConstPtr::null_ptr())
+  }
+}
+
+impl<T> Debug for AtomicConstPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
+
+/// An atomically replaceable `MutPtr`
+pub struct AtomicMutPtr<T>(AtomicPtr<T>);
+
+impl<T> AtomicMutPtr<T> {
+  pub const fn new(ptr: MutPtr<T>) -> Self {
+    Self(AtomicPtr::new(ptr.raw()))
+  }
+
+  pub fn load(&self, order: Ordering) -> MutPtr<T> {
+    MutPtr::from(self.0.load(order))
+  }
+
+  pub fn store(&self, ptr: MutPtr<T>, order: Ordering) {
+    self.0.store(ptr.raw(), order);
+  }
+
+  pub fn swap(&self, ptr: MutPtr<T>, order: Ordering) -> MutPtr<T> {
+    MutPtr::from(self.0.swap(ptr.raw(), order))
+  }
+
+  /// Stores `new` if the slot still holds `current`, either way returns what the slot held before
+  pub fn compare_exchange(
+    &self,
+    current: MutPtr<T>,
+    new: MutPtr<T>,
+    success: Ordering,
+    failure: Ordering,
+  ) -> Result<MutPtr<T>, MutPtr<T>> {
+    self
+      .0
+      .compare_exchange(current.raw(), new.raw(), success, failure)
+      .map(MutPtr::from)
+      .map_err(MutPtr::from)
+  }
+
+  pub fn into_inner(self) -> MutPtr<T> {
+    MutPtr::from(self.0.into_inner())
+  }
+}
+
+impl<T> Default for AtomicMutPtr<T> {
+  fn default() -> Self {
+    Self::new(MutPtr::null_ptr())
+  }
+}
+
+impl<T> Debug for AtomicMutPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
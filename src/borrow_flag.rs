@@ -0,0 +1,166 @@
+//! Runtime borrow tracking for pointers made by `MutPtr::tracked`
+//!
+//! With the `borrow-checked` feature, every tracked address has a `RefCell`-style flag on its thread: positive while
+//! `borrow` guards are out, negative while a `borrow_mut` guard is. Untracked pointers, and every pointer without the
+//! feature, skip the flag entirely and their guards are zero sized.
+
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "borrow-checked")]
+mod imp {
+  use std::{cell::RefCell, collections::HashMap};
+
+  std::thread_local! {
+    // an address without an entry is not borrowed
+    static FLAGS: RefCell<HashMap<usize, isize>> = RefCell::new(HashMap::new());
+  }
+
+  fn flag(addr: usize) -> isize {
+    FLAGS.with(|flags| flags.borrow().get(&addr).copied().unwrap_or(0))
+  }
+
+  fn set_flag(addr: usize, value: isize) {
+    FLAGS.with(|flags| {
+      let mut flags = flags.borrow_mut();
+      if value == 0 {
+        flags.remove(&addr);
+      } else {
+        flags.insert(addr, value);
+      }
+    });
+  }
+
+  #[derive(Clone, Copy)]
+  pub(crate) struct Tracking(Option<usize>);
+
+  impl Tracking {
+    pub(crate) const fn untracked() -> Self {
+      Self(None)
+    }
+
+    pub(crate) fn track(ptr: *const ()) -> Self {
+      Self(Some(ptr.addr()))
+    }
+
+    /// Panics if a `borrow_mut` guard is out
+    #[track_caller]
+    pub(crate) fn check_shared(&self) {
+      if let Some(addr) = self.0 {
+        if flag(addr) < 0 {
+          panic!("MutPtr already mutably borrowed");
+        }
+      }
+    }
+
+    /// Panics if any guard is out
+    #[track_caller]
+    pub(crate) fn check_exclusive(&self) {
+      if let Some(addr) = self.0 {
+        match flag(addr) {
+          0 => {}
+          flag if flag < 0 => panic!("MutPtr already mutably borrowed"),
+          _ => panic!("MutPtr already borrowed"),
+        }
+      }
+    }
+
+    #[track_caller]
+    pub(crate) fn borrow(&self) -> BorrowToken {
+      self.check_shared();
+      if let Some(addr) = self.0 {
+        set_flag(addr, flag(addr) + 1);
+      }
+      BorrowToken(self.0)
+    }
+
+    #[track_caller]
+    pub(crate) fn borrow_mut(&self) -> BorrowToken {
+      self.check_exclusive();
+      if let Some(addr) = self.0 {
+        set_flag(addr, -1);
+      }
+      BorrowToken(self.0)
+    }
+  }
+
+  /// Gives back one shared or the exclusive borrow once the guard holding it is dropped
+  pub(crate) struct BorrowToken(Option<usize>);
+
+  impl Drop for BorrowToken {
+    fn drop(&mut self) {
+      if let Some(addr) = self.0 {
+        let flag = flag(addr);
+        set_flag(addr, if flag < 0 { 0 } else { flag - 1 });
+      }
+    }
+  }
+}
+
+#[cfg(not(feature = "borrow-checked"))]
+mod imp {
+  #[derive(Clone, Copy)]
+  pub(crate) struct Tracking;
+
+  impl Tracking {
+    pub(crate) const fn untracked() -> Self {
+      Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn track(_ptr: *const ()) -> Self {
+      Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn check_shared(&self) {}
+
+    #[inline(always)]
+    pub(crate) fn check_exclusive(&self) {}
+
+    #[inline(always)]
+    pub(crate) fn borrow(&self) -> BorrowToken {
+      BorrowToken
+    }
+
+    #[inline(always)]
+    pub(crate) fn borrow_mut(&self) -> BorrowToken {
+      BorrowToken
+    }
+  }
+
+  pub(crate) struct BorrowToken;
+}
+
+pub(crate) use imp::{BorrowToken, Tracking};
+
+/// A shared borrow through `MutPtr::borrow`
+pub struct PtrRef<'a, T: ?Sized> {
+  pub(crate) value: &'a T,
+  pub(crate) _token: BorrowToken,
+}
+
+impl<T: ?Sized> Deref for PtrRef<'_, T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    self.value
+  }
+}
+
+/// An exclusive borrow through `MutPtr::borrow_mut`
+pub struct PtrRefMut<'a, T: ?Sized> {
+  pub(crate) value: &'a mut T,
+  pub(crate) _token: BorrowToken,
+}
+
+impl<T: ?Sized> Deref for PtrRefMut<'_, T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    self.value
+  }
+}
+
+impl<T: ?Sized> DerefMut for PtrRefMut<'_, T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.value
+  }
+}
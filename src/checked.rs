@@ -0,0 +1,178 @@
+//! Per-pointer bookkeeping for the `checked` feature
+//!
+//! With the feature enabled in a debug build, every pointer made through `new` records the generation its address
+//! had at construction time. `clear()` and `invalidate()` retire that generation, so any copy still floating around
+//! panics on its next dereference instead of reading freed memory. Otherwise `Meta` is zero sized and every method is
+//! a no-op.
+
+use super::borrow_flag::Tracking;
+
+#[cfg(all(feature = "checked", debug_assertions))]
+mod imp {
+  use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    panic::Location,
+    thread::{self, ThreadId},
+  };
+
+  std::thread_local! {
+    // only addresses with registrations that have not been invalidated yet, so the table stays as small as the set of
+    // live pointees
+    static GENERATIONS: RefCell<HashMap<usize, u64>> = RefCell::new(HashMap::new());
+    // never reused, so a registration outliving its entry can't match a later one at the same address
+    static NEXT_GENERATION: Cell<u64> = const { Cell::new(0) };
+  }
+
+  #[derive(Clone, Copy)]
+  struct Registration {
+    addr: usize,
+    generation: u64,
+    thread: ThreadId,
+    origin: &'static Location<'static>,
+  }
+
+  #[derive(Clone, Copy)]
+  pub(crate) struct Generation(Option<Registration>);
+
+  impl Generation {
+    pub(crate) const fn unregistered() -> Self {
+      Self(None)
+    }
+
+    #[track_caller]
+    pub(crate) fn register(ptr: *const ()) -> Self {
+      let addr = ptr.addr();
+      let generation = GENERATIONS.with(|table| {
+        *table.borrow_mut().entry(addr).or_insert_with(|| {
+          let generation = NEXT_GENERATION.get();
+          NEXT_GENERATION.set(generation + 1);
+          generation
+        })
+      });
+      Self(Some(Registration {
+        addr,
+        generation,
+        thread: thread::current().id(),
+        origin: Location::caller(),
+      }))
+    }
+
+    /// Only pointers registered on this thread can be checked, lookups from other threads are skipped
+    pub(crate) fn is_current(&self) -> bool {
+      self.0.is_none_or(|reg| {
+        reg.thread != thread::current().id() || GENERATIONS.with(|table| table.borrow().get(&reg.addr) == Some(&reg.generation))
+      })
+    }
+
+    #[track_caller]
+    pub(crate) fn verify(&self, kind: &str) {
+      if let Some(reg) = &self.0 {
+        if !self.is_current() {
+          panic!("dereferenced an invalidated {} constructed at {}", kind, reg.origin);
+        }
+      }
+    }
+
+    /// Retires every registration of the address at once, the next `register` starts a fresh generation
+    pub(crate) fn invalidate(&mut self) {
+      if let Some(reg) = self.0.take() {
+        if reg.thread == thread::current().id() {
+          GENERATIONS.with(|table| {
+            let mut table = table.borrow_mut();
+            if table.get(&reg.addr) == Some(&reg.generation) {
+              table.remove(&reg.addr);
+            }
+          });
+        }
+      }
+    }
+  }
+
+  #[cfg(test)]
+  pub(crate) fn tracked_addresses() -> usize {
+    GENERATIONS.with(|table| table.borrow().len())
+  }
+}
+
+#[cfg(not(all(feature = "checked", debug_assertions)))]
+mod imp {
+  #[derive(Clone, Copy)]
+  pub(crate) struct Generation;
+
+  impl Generation {
+    pub(crate) const fn unregistered() -> Self {
+      Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn register(_ptr: *const ()) -> Self {
+      Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_current(&self) -> bool {
+      true
+    }
+
+    #[inline(always)]
+    pub(crate) fn verify(&self, _kind: &str) {}
+
+    #[inline(always)]
+    pub(crate) fn invalidate(&mut self) {}
+  }
+}
+
+#[cfg(all(test, feature = "checked", debug_assertions))]
+pub(crate) use imp::tracked_addresses;
+
+/// Everything a pointer carries besides its address, zero sized unless `checked` or `borrow-checked` is enabled
+#[derive(Clone, Copy)]
+pub(crate) struct Meta {
+  generation: imp::Generation,
+  borrow: Tracking,
+}
+
+impl Meta {
+  pub(crate) const fn unregistered() -> Self {
+    Self {
+      generation: imp::Generation::unregistered(),
+      borrow: Tracking::untracked(),
+    }
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub(crate) fn register(ptr: *const ()) -> Self {
+    Self {
+      generation: imp::Generation::register(ptr),
+      borrow: Tracking::untracked(),
+    }
+  }
+
+  /// Like `register`, and borrows through this pointer and its copies are checked at runtime from now on
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub(crate) fn register_tracked(ptr: *const ()) -> Self {
+    Self {
+      generation: imp::Generation::register(ptr),
+      borrow: Tracking::track(ptr),
+    }
+  }
+
+  /// Whether a dereference would pass `verify`
+  pub(crate) fn is_current(&self) -> bool {
+    self.generation.is_current()
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub(crate) fn verify(&self, kind: &str) {
+    self.generation.verify(kind);
+  }
+
+  pub(crate) fn invalidate(&mut self) {
+    self.generation.invalidate();
+  }
+
+  pub(crate) fn tracking(&self) -> &Tracking {
+    &self.borrow
+  }
+}
@@ -0,0 +1,36 @@
+//! Where still-live `SmartPtr` allocations were made, for tracking down leaks
+//!
+//! With the `backtrace` feature every `SmartPtr` constructor records its caller's `Location`, which is forgotten again
+//! once the last strong reference is gone. Without the feature nothing is recorded and this module does not exist.
+
+use super::{smart::RefCount, MutPtr};
+use std::{
+  collections::BTreeMap,
+  panic::Location,
+  sync::{Mutex, MutexGuard, PoisonError},
+  vec::Vec,
+};
+
+// keyed by the address of the count, which no other live allocation can share
+static ORIGINS: Mutex<BTreeMap<usize, &'static Location<'static>>> = Mutex::new(BTreeMap::new());
+
+fn origins() -> MutexGuard<'static, BTreeMap<usize, &'static Location<'static>>> {
+  ORIGINS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(crate) fn record(rc: MutPtr<RefCount>, origin: &'static Location<'static>) {
+  origins().insert(rc.addr(), origin);
+}
+
+pub(crate) fn forget(rc: MutPtr<RefCount>) {
+  origins().remove(&rc.addr());
+}
+
+pub(crate) fn origin(rc: MutPtr<RefCount>) -> Option<&'static Location<'static>> {
+  origins().get(&rc.addr()).copied()
+}
+
+/// The call sites of every allocation that still has a strong reference, on any thread
+pub fn live_origins() -> Vec<&'static Location<'static>> {
+  origins().values().copied().collect()
+}
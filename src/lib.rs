@@ -1,286 +1,1771 @@
-use std::{
-  cell::RefCell,
-  fmt::{Debug, Display, Error, Formatter},
-  ops::{Deref, DerefMut},
-  ptr,
-  rc::Rc,
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(coerce_unsized, layout_for_ptr, unsize))]
+#![cfg_attr(
+  all(feature = "nightly", not(any(feature = "checked", feature = "borrow-checked"))),
+  feature(dispatch_from_dyn)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, string::String, vec::Vec};
+use core::{
+  any::{Any, TypeId},
+  cell::{Cell, RefCell, UnsafeCell},
+  cmp::Ordering,
+  ffi::c_void,
+  fmt::{self, Debug, Display, Error, Formatter},
+  hash::{Hash, Hasher},
+  mem::{self, MaybeUninit},
+  ops::{Deref, DerefMut, Index, IndexMut, RangeBounds},
+  pin::Pin,
+  ptr, slice,
 };
 
+use checked::Meta;
+use traits::RawAlign;
+
+#[cfg(target_has_atomic = "ptr")]
+mod atomic;
+mod borrow_flag;
+mod checked;
+#[cfg(feature = "backtrace")]
+pub mod diagnostics;
+mod non_null;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "alloc")]
+mod pool;
+mod refs;
+mod send;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde_shared;
+#[cfg(feature = "alloc")]
+mod smart;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "alloc")]
+mod sync;
+mod tagged;
+pub mod traits;
+#[cfg(feature = "nightly")]
+mod unsize;
+
+#[cfg(target_has_atomic = "ptr")]
+pub use atomic::{AtomicConstPtr, AtomicMutPtr};
+pub use borrow_flag::{PtrRef, PtrRefMut};
+pub use non_null::{NonNullConstPtr, NonNullMutPtr};
+#[cfg(feature = "alloc")]
+pub use pool::SmartPool;
+#[cfg(feature = "derive")]
+pub use ptr_derive::AsPtr;
+pub use refs::{RefMutPtr, RefPtr};
+pub use send::{SendPtr, SyncPtr};
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub use serde_impls::SmartPtrSeed;
+#[cfg(feature = "alloc")]
+pub use smart::{SmartProjection, SmartPtr, SmartPtrRepr, WeakPtr};
+#[cfg(feature = "std")]
+pub use sync::AtomicSmartPtr;
+#[cfg(feature = "alloc")]
+pub use sync::SyncSmartPtr;
+pub use tagged::TaggedPtr;
+
 pub mod prelude {
   pub use super::*;
 }
 
-pub struct ConstPtr<T: ?Sized>(*const T);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PtrError {
+  Null,
+  Misaligned,
+  /// The pointee does not fit the target type exactly, or a slice would leave leftover bytes
+  SizeMismatch,
+}
+
+impl Display for PtrError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self {
+      Self::Null => write!(f, "pointer is null"),
+      Self::Misaligned => write!(f, "pointer is misaligned"),
+      Self::SizeMismatch => write!(f, "pointee size does not match the target type"),
+    }
+  }
+}
+
+impl core::error::Error for PtrError {}
+
+/// Without the `checked` and `borrow-checked` features this is `#[repr(transparent)]` over `*const T`: same size,
+/// alignment and ABI, so it can sit in `#[repr(C)]` structs and be transmuted from `*const T` or `Option<&T>`, with null
+/// a valid value. Either feature adds bookkeeping next to the address and drops the guarantee.
+#[cfg_attr(not(any(feature = "checked", feature = "borrow-checked")), repr(transparent))]
+pub struct ConstPtr<T: ?Sized>(*const T, Meta);
+
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+const _: () = {
+  assert!(mem::size_of::<ConstPtr<u8>>() == mem::size_of::<*const u8>());
+  assert!(mem::align_of::<ConstPtr<u8>>() == mem::align_of::<*const u8>());
+  assert!(mem::size_of::<ConstPtr<[u8]>>() == mem::size_of::<*const [u8]>());
+  assert!(mem::size_of::<ConstPtr<dyn Any>>() == mem::size_of::<*const dyn Any>());
+};
+
+// invalidates the copies handed out by `scoped` once `f` returns or unwinds
+struct Scope(Meta);
+
+impl Drop for Scope {
+  fn drop(&mut self) {
+    self.0.invalidate();
+  }
+}
 
 impl<T> Default for ConstPtr<T> {
   fn default() -> Self {
-    Self(ptr::null())
+    Self::null_ptr()
   }
 }
 
 impl<T> ConstPtr<T> {
+  /// Same as `default()`, but usable in `const` items
+  pub const fn null_ptr() -> Self {
+    Self(ptr::null(), Meta::unregistered())
+  }
+}
+
+impl<T: ?Sized> ConstPtr<T> {
+  #[cfg_attr(feature = "checked", track_caller)]
   pub fn new(t: &T) -> Self {
-    Self(t)
+    let ptr: *const T = t;
+    Self(ptr, Meta::register(ptr.cast()))
+  }
+
+  /// Rejects null and misaligned addresses up front, a `TryFrom<*const T>` is ruled out by the blanket impl that comes with
+  /// `From<*const T>`
+  pub fn try_from_raw(raw: *const T) -> Result<Self, PtrError>
+  where
+    T: RawAlign,
+  {
+    if raw.is_null() {
+      Err(PtrError::Null)
+    } else if !T::is_aligned_raw(raw) {
+      Err(PtrError::Misaligned)
+    } else {
+      Ok(Self::from_raw_unchecked(raw))
+    }
+  }
+
+  /// Like `From<*const T>` without the alignment assertion, for addresses fixed up before they are dereferenced
+  pub const fn from_raw_unchecked(raw: *const T) -> Self {
+    Self(raw, Meta::unregistered())
+  }
+
+  /// Like `new`, but usable in `const` items, which leaves the pointer out of the `checked` bookkeeping
+  pub const fn from_ref(t: &T) -> Self {
+    Self(t, Meta::unregistered())
   }
 
-  pub fn raw(&self) -> *const T {
+  /// Lends `t` to `f` as a pointer. With the `checked` feature in a debug build, any copy smuggled out of `f` panics
+  /// on its next dereference.
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn scoped<R>(t: &T, f: impl FnOnce(Self) -> R) -> R {
+    let ptr = Self::new(t);
+    let _scope = Scope(ptr.1);
+    f(ptr)
+  }
+
+  pub const fn raw(&self) -> *const T {
     self.0
   }
 
-  pub fn null(&self) -> bool {
+  pub const fn null(&self) -> bool {
     self.0.is_null()
   }
 
-  pub fn present(&self) -> bool {
+  pub const fn present(&self) -> bool {
     !self.null()
   }
 
+  /// Nulls the pointer out, with the `checked` feature every copy made before the call panics on its next dereference
   pub fn clear(&mut self) {
-    self.0 = ptr::null();
+    self.1.invalidate();
+    self.0 = self.0.with_addr(0);
+  }
+
+  /// Same as `clear()`, for call sites where retiring the copies is the point
+  pub fn invalidate(&mut self) {
+    self.clear();
+  }
+
+  /// The address without its provenance, for logging and range checks rather than rebuilding a pointer
+  pub fn addr(&self) -> usize {
+    self.0.addr()
+  }
+
+  /// Moves the pointer to `addr` while keeping the provenance of the original
+  pub fn with_addr(self, addr: usize) -> Self {
+    Self(self.0.with_addr(addr), self.1)
+  }
+
+  pub fn map_addr(self, f: impl FnOnce(usize) -> usize) -> Self {
+    Self(self.0.map_addr(f), self.1)
+  }
+
+  /// Like `addr`, but marks the provenance as exposed so `with_exposed_provenance` may rebuild the pointer later
+  pub fn expose_provenance(&self) -> usize {
+    self.0.expose_provenance()
+  }
+
+  /// The preferred way to access the pointee, `None` when the pointer is null
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn as_opt(&self) -> Option<&T> {
+    if self.present() {
+      self.1.verify("ConstPtr");
+      Some(unsafe { &*self.0 })
+    } else {
+      None
+    }
+  }
+
+  pub fn try_deref(&self) -> Result<&T, PtrError> {
+    self.as_opt().ok_or(PtrError::Null)
+  }
+
+  /// Like `try_deref`, but a pointer the `checked` feature knows to be invalidated is reported as `PtrError::Null`
+  /// instead of panicking
+  pub fn checked_ref(&self) -> Result<&T, PtrError> {
+    if self.present() && self.1.is_current() {
+      self.1.tracking().check_shared();
+      Ok(unsafe { &*self.0 })
+    } else {
+      Err(PtrError::Null)
+    }
+  }
+
+  /// Panics with `msg` if the pointer is null
+  #[track_caller]
+  pub fn expect(&self, msg: &str) -> &T {
+    self.as_opt().expect(msg)
+  }
+
+  /// Panics if the pointer is null.
+  ///
+  /// # Safety
+  ///
+  /// The pointee must already be pinned, it may never be moved again even once the `Pin` is gone
+  #[track_caller]
+  pub unsafe fn as_pin_ref(&self) -> Pin<&T> {
+    Pin::new_unchecked(self.expect("as_pin_ref on a null ConstPtr"))
+  }
+
+  /// Like `as_opt`, but the reference is detached from `self`.
+  ///
+  /// # Safety
+  ///
+  /// `'a` is chosen by the caller, nothing stops the reference from outliving the pointee
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub unsafe fn into_opt<'a>(self) -> Option<&'a T> {
+    if self.present() {
+      self.1.verify("ConstPtr");
+    }
+    self.0.as_ref()
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn to_option_copied(&self) -> Option<T>
+  where
+    T: Copy,
+  {
+    self.as_opt().copied()
+  }
+
+  /// Calls `f` with the pointee, skipped for a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn map_present<R>(self, f: impl FnOnce(&T) -> R) -> Option<R> {
+    self.as_opt().map(f)
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+    if let Some(value) = self.as_opt() {
+      f(value);
+    }
+    self
+  }
+
+  /// Nulls the pointer out unless the pointee passes `pred`, which is never called for a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn filter(mut self, pred: impl FnOnce(&T) -> bool) -> Self {
+    if !self.as_opt().is_some_and(pred) {
+      // only this copy is nulled, the others still point at a live pointee
+      self.0 = self.0.with_addr(0);
+    }
+    self
+  }
+
+  pub fn or(self, fallback: Self) -> Self {
+    if self.present() {
+      self
+    } else {
+      fallback
+    }
+  }
+
+  pub fn cast<U>(self) -> ConstPtr<U> {
+    ConstPtr(self.0.cast(), self.1)
+  }
+
+  /// Like `cast`, but panics if the resulting pointer is not aligned for `U`
+  pub fn cast_aligned<U>(self) -> ConstPtr<U> {
+    let ptr = self.cast::<U>();
+    assert!(ptr.0.is_aligned(), "pointer is not aligned to {} bytes", mem::align_of::<U>());
+    ptr
+  }
+
+  pub fn erase(self) -> ConstPtr<()> {
+    self.cast()
+  }
+
+  /// The address as C user data, for handing to callbacks that give it back through `from_void`
+  pub fn into_void(self) -> *const c_void {
+    self.0.cast()
+  }
+
+  /// Points at a part of the pointee, a null pointer stays null without calling `f`
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn map<U>(self, f: impl FnOnce(&T) -> &U) -> ConstPtr<U> {
+    match self.as_opt() {
+      Some(value) => ConstPtr(f(value), self.1),
+      None => ConstPtr::default(),
+    }
   }
 }
 
-impl<T> AsRef<T> for ConstPtr<T> {
-  fn as_ref(&self) -> &T {
-    unsafe { &*self.raw() }
+/// Arithmetic on null pointers trips a debug assertion in the unchecked variants, the wrapping variants keep them null
+impl<T> ConstPtr<T> {
+  /// Rebuilds a pointer from an address previously passed out by `expose_provenance`
+  pub fn with_exposed_provenance(addr: usize) -> Self {
+    Self(ptr::with_exposed_provenance(addr), Meta::unregistered())
+  }
+
+  /// Takes back C user data, a null pointer gives a null `ConstPtr`
+  ///
+  /// # Safety
+  ///
+  /// A non-null `data` must point at a `T`, typically it came out of `into_void` on a `ConstPtr<T>`.
+  pub unsafe fn from_void(data: *const c_void) -> Self {
+    Self::from(data.cast::<T>())
+  }
+
+  /// Whether the address is a multiple of `align_of::<T>()`, a null pointer counts as aligned
+  pub fn is_aligned(&self) -> bool {
+    self.0.is_aligned()
+  }
+
+  /// Elements to advance by until the address is a multiple of `align`, same contract as `pointer::align_offset`
+  pub fn align_offset(&self, align: usize) -> usize {
+    self.0.align_offset(align)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::add`
+  pub unsafe fn add(self, count: usize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null ConstPtr");
+    Self(self.0.add(count), self.1)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::sub`
+  pub unsafe fn sub(self, count: usize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null ConstPtr");
+    Self(self.0.sub(count), self.1)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::offset`
+  pub unsafe fn offset(self, count: isize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null ConstPtr");
+    Self(self.0.offset(count), self.1)
+  }
+
+  /// Distance to `origin` in elements, negative if `origin` comes after `self`
+  ///
+  /// # Safety
+  ///
+  /// Same contract as `pointer::offset_from`
+  pub unsafe fn offset_from(&self, origin: ConstPtr<T>) -> isize {
+    self.0.offset_from(origin.0)
+  }
+
+  /// Where `self` sits in `base`, `None` if it points outside the slice or between two elements.
+  ///
+  /// Zero-sized elements all share one address, so any pointer to it counts as index 0.
+  pub fn element_index(&self, base: ConstPtr<[T]>) -> Option<usize> {
+    let start = base.raw().cast::<T>().addr();
+    let offset = self.addr().checked_sub(start)?;
+    let index = match mem::size_of::<T>() {
+      0 if offset == 0 => 0,
+      0 => return None,
+      size if offset % size == 0 => offset / size,
+      _ => return None,
+    };
+
+    (base.present() && index < base.len()).then_some(index)
+  }
+
+  pub fn wrapping_add(self, count: usize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_add(count), self.1)
+  }
+
+  pub fn wrapping_sub(self, count: usize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_sub(count), self.1)
+  }
+
+  pub fn wrapping_offset(self, count: isize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_offset(count), self.1)
+  }
+
+  /// Reads without letting the compiler elide or reorder the access, such as from device registers
+  pub fn read_volatile(&self) -> T
+  where
+    T: Copy,
+  {
+    debug_assert!(self.present(), "volatile read through a null ConstPtr");
+    debug_assert!(self.0.is_aligned(), "volatile read through a misaligned ConstPtr");
+    unsafe { ptr::read_volatile(self.0) }
+  }
+
+  /// Reads from an address of any alignment, such as fields of packed formats, build the pointer with
+  /// `from_raw_unchecked` to skip the alignment assertion in `From`
+  pub fn read_unaligned(&self) -> T
+  where
+    T: Copy,
+  {
+    debug_assert!(self.present(), "unaligned read through a null ConstPtr");
+    unsafe { ptr::read_unaligned(self.0) }
+  }
+
+  /// The `size_of::<T>()` bytes of the pointee, or an empty null view of a null pointer
+  ///
+  /// # Safety
+  ///
+  /// `T` must have no padding, reading uninitialized padding bytes through the view is undefined behavior. With the
+  /// `bytemuck` feature, `cast_pod::<[u8; N]>` is the safe alternative for `NoUninit` types.
+  pub unsafe fn as_bytes(&self) -> ConstPtr<[u8]> {
+    let len = if self.present() { mem::size_of::<T>() } else { 0 };
+    ConstPtr(ptr::slice_from_raw_parts(self.0.cast(), len), self.1)
   }
 }
 
-impl<T> Clone for ConstPtr<T> {
-  fn clone(&self) -> Self {
-    *self
+impl ConstPtr<[u8]> {
+  /// Reads a `U` starting `offset` bytes in, at any alignment. Panics if it would run past the end.
+  ///
+  /// # Safety
+  ///
+  /// The bytes must form a valid `U`, which holds for plain-old-data such as integers and floats
+  pub unsafe fn read_unaligned_at<U: Copy>(&self, offset: usize) -> U {
+    assert!(
+      offset.checked_add(mem::size_of::<U>()).is_some_and(|end| end <= self.len()),
+      "unaligned read of {} bytes at offset {} out of bounds of {} bytes",
+      mem::size_of::<U>(),
+      offset,
+      self.len()
+    );
+    ptr::read_unaligned(self.0.cast::<u8>().add(offset).cast::<U>())
   }
 }
 
-impl<T> Copy for ConstPtr<T> {}
+impl<T> ConstPtr<[T]> {
+  pub fn from_slice(slice: &[T]) -> Self {
+    Self::new(slice)
+  }
 
-impl<T> Deref for ConstPtr<T> {
-  type Target = T;
-  fn deref(&self) -> &Self::Target {
-    unsafe { &*self.0 }
+  /// Read from the pointer metadata, the slice is never dereferenced
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn get(&self, index: usize) -> Option<ConstPtr<T>> {
+    if self.present() && index < self.len() {
+      Some(ConstPtr(self.0.cast::<T>().wrapping_add(index), self.1))
+    } else {
+      None
+    }
+  }
+
+  /// Borrows the elements, a null pointer yields nothing
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.as_opt().unwrap_or_default().iter()
   }
 }
 
-impl<T> From<MutPtr<T>> for ConstPtr<T> {
-  fn from(ptr: MutPtr<T>) -> Self {
-    Self(ptr.raw())
+impl<T, const N: usize> ConstPtr<[T; N]> {
+  /// The same elements with the length moved into the pointer metadata
+  pub fn as_slice_ptr(&self) -> ConstPtr<[T]> {
+    ConstPtr(self.0 as *const [T], self.1)
   }
 }
 
-impl<T> From<Rc<T>> for ConstPtr<T> {
-  fn from(ptr: Rc<T>) -> Self {
-    Self(ptr.as_ref())
+impl ConstPtr<str> {
+  /// Length in bytes, read from the pointer metadata
+  pub fn len(&self) -> usize {
+    self.as_bytes_ptr().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn as_bytes_ptr(&self) -> ConstPtr<[u8]> {
+    ConstPtr(self.0 as *const [u8], self.1)
   }
 }
 
-impl<T> From<&Box<T>> for ConstPtr<T> {
-  fn from(ptr: &Box<T>) -> Self {
-    Self(ptr.as_ref())
+impl ConstPtr<dyn Any> {
+  /// `None` for a null pointer rather than dereferencing it
+  pub fn type_id(&self) -> Option<TypeId> {
+    self.as_opt().map(Any::type_id)
+  }
+
+  /// Retypes the pointer if the pointee is a `T`, `None` on a mismatch or a null pointer
+  pub fn downcast_ref<T: Any>(&self) -> Option<ConstPtr<T>> {
+    if self.as_opt()?.is::<T>() {
+      Some(self.cast())
+    } else {
+      None
+    }
   }
 }
 
-impl<T> From<*const T> for ConstPtr<T> {
-  fn from(value: *const T) -> Self {
-    Self(value)
+impl<T> ConstPtr<MaybeUninit<T>> {
+  /// # Safety
+  ///
+  /// The pointer must be present and the pointee initialized, same contract as `MaybeUninit::assume_init_ref`
+  pub unsafe fn assume_init_ref(&self) -> &T {
+    (*self.0).assume_init_ref()
   }
 }
 
-pub struct MutPtr<T: ?Sized>(*mut T);
+/// Opt-in through `shared-const`, the caller is responsible for the pointee outliving every thread that sees the pointer
+#[cfg(feature = "shared-const")]
+unsafe impl<T: ?Sized + Sync> Sync for ConstPtr<T> {}
 
-impl<T> Default for MutPtr<T> {
-  fn default() -> Self {
-    Self(ptr::null_mut())
+#[cfg(feature = "shared-const")]
+unsafe impl<T: ?Sized + Sync> Send for ConstPtr<T> {}
+
+impl<T: ?Sized> AsRef<T> for ConstPtr<T> {
+  #[cfg_attr(any(feature = "checked", debug_assertions), track_caller)]
+  fn as_ref(&self) -> &T {
+    debug_assert!(self.present(), "dereferenced a null ConstPtr");
+    self.1.verify("ConstPtr");
+    unsafe { &*self.raw() }
   }
 }
 
-impl<T> Clone for MutPtr<T> {
+impl<T: ?Sized> Clone for ConstPtr<T> {
   fn clone(&self) -> Self {
     *self
   }
 }
 
-impl<T> Copy for MutPtr<T> {}
+impl<T: ?Sized> Copy for ConstPtr<T> {}
 
-impl<T> MutPtr<T> {
-  pub fn new(t: &mut T) -> Self {
-    Self(t)
+/// Compares by address, not by the pointee's value; all null pointers are equal
+impl<T: ?Sized> PartialEq for ConstPtr<T> {
+  fn eq(&self, other: &Self) -> bool {
+    ptr::addr_eq(self.0, other.0)
   }
+}
 
-  pub fn raw(&self) -> *mut T {
-    self.0
+impl<T: ?Sized> Eq for ConstPtr<T> {}
+
+/// Prints the address and the pointee, or `ConstPtr(null)` without dereferencing
+impl<T: ?Sized + Debug> Debug for ConstPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self.as_opt() {
+      Some(value) => {
+        write!(f, "ConstPtr({:p} -> ", self.0.cast::<()>())?;
+        value.fmt(f)?;
+        write!(f, ")")
+      }
+      None => write!(f, "ConstPtr(null)"),
+    }
   }
+}
 
-  pub fn null(&self) -> bool {
-    self.0.is_null()
+/// Forwards to the pointee, or writes `<null>` without dereferencing
+impl<T: ?Sized + Display> Display for ConstPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self.as_opt() {
+      Some(value) => value.fmt(f),
+      None => f.pad("<null>"),
+    }
   }
+}
 
-  pub fn present(&self) -> bool {
-    !self.null()
+impl<T: ?Sized> fmt::Pointer for ConstPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    fmt::Pointer::fmt(&self.0.cast::<()>(), f)
   }
+}
 
-  pub fn clear(&mut self) {
-    self.0 = ptr::null_mut();
+/// Orders by address, so null pointers sort first
+impl<T: ?Sized> PartialOrd for ConstPtr<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
   }
 }
 
-impl<T> AsRef<T> for MutPtr<T> {
-  fn as_ref(&self) -> &T {
-    unsafe { &*self.raw() }
+impl<T: ?Sized> Ord for ConstPtr<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.cast::<()>().cmp(&other.0.cast::<()>())
   }
 }
 
-impl<T> AsMut<T> for MutPtr<T> {
-  fn as_mut(&mut self) -> &mut T {
-    unsafe { &mut *self.raw() }
+/// Hashes the address, consistent with the address-based `PartialEq`
+impl<T: ?Sized> Hash for ConstPtr<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.cast::<()>().hash(state);
   }
 }
 
-impl<T> Deref for MutPtr<T> {
+impl<T: ?Sized> Deref for ConstPtr<T> {
   type Target = T;
+  #[cfg_attr(any(feature = "checked", debug_assertions), track_caller)]
   fn deref(&self) -> &Self::Target {
+    debug_assert!(self.present(), "dereferenced a null ConstPtr");
+    self.1.verify("ConstPtr");
     unsafe { &*self.0 }
   }
 }
 
-impl<T> DerefMut for MutPtr<T> {
-  fn deref_mut(&mut self) -> &mut Self::Target {
-    unsafe { &mut *self.0 }
+impl<T> Index<usize> for ConstPtr<[T]> {
+  type Output = T;
+  fn index(&self, index: usize) -> &Self::Output {
+    &self.as_ref()[index]
   }
 }
 
-impl<T> From<&mut Box<T>> for MutPtr<T> {
-  fn from(ptr: &mut Box<T>) -> Self {
-    Self(ptr.as_mut())
+impl<'a, T> IntoIterator for &'a ConstPtr<[T]> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
   }
 }
 
-impl<T> From<Rc<RefCell<T>>> for MutPtr<T> {
-  fn from(ptr: Rc<RefCell<T>>) -> Self {
-    Self(ptr.as_ptr())
+impl<T: ?Sized> From<MutPtr<T>> for ConstPtr<T> {
+  fn from(ptr: MutPtr<T>) -> Self {
+    Self(ptr.0, ptr.1)
   }
 }
 
-impl<T> From<*mut T> for MutPtr<T> {
-  fn from(value: *mut T) -> Self {
-    Self(value)
+/// Consumes the `Rc`, so the pointer dangles unless another `Rc` keeps the value alive; prefer `From<&Rc<T>>`
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<Rc<T>> for ConstPtr<T> {
+  fn from(ptr: Rc<T>) -> Self {
+    Self::new(ptr.as_ref())
   }
 }
 
-pub trait AsPtr {
-  fn as_ptr(&self) -> ConstPtr<Self>
-  where
-    Self: Sized,
-  {
-    ConstPtr(self)
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<&Rc<T>> for ConstPtr<T> {
+  fn from(ptr: &Rc<T>) -> Self {
+    Self::new(ptr.as_ref())
   }
+}
 
-  fn as_ptr_mut(&mut self) -> MutPtr<Self>
-  where
-    Self: Sized,
-  {
-    MutPtr(self)
+/// Borrows the contents without touching the count, the pointer does not keep the `Arc` alive
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T: ?Sized> From<&Arc<T>> for ConstPtr<T> {
+  fn from(ptr: &Arc<T>) -> Self {
+    Self::new(ptr.as_ref())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<&Box<T>> for ConstPtr<T> {
+  fn from(ptr: &Box<T>) -> Self {
+    Self::new(ptr.as_ref())
   }
 }
 
-pub struct SmartPtr<T> {
-  ptr: MutPtr<T>,
-  rc: MutPtr<usize>,
+#[cfg(feature = "alloc")]
+impl<T> From<&Vec<T>> for ConstPtr<[T]> {
+  fn from(items: &Vec<T>) -> Self {
+    Self::from_slice(items)
+  }
 }
 
-impl<T> SmartPtr<T> {
-  pub fn new(item: T) -> Self {
-    let ptr = MutPtr::new(Box::leak(Box::new(item)));
-    let rc = Self::new_ref_count();
+impl<T> From<&[T]> for ConstPtr<[T]> {
+  fn from(slice: &[T]) -> Self {
+    Self::from_slice(slice)
+  }
+}
 
-    Self { ptr, rc }
+impl<T, const N: usize> From<&[T; N]> for ConstPtr<[T]> {
+  fn from(array: &[T; N]) -> Self {
+    Self::from_slice(array)
   }
+}
 
-  pub fn valid(&self) -> bool {
-    self.ptr.present() && self.rc.present() && *self.rc > 0
+impl From<&str> for ConstPtr<str> {
+  fn from(text: &str) -> Self {
+    Self::new(text)
   }
+}
 
-  pub fn access(&self) -> &T {
-    &self.ptr
+#[cfg(feature = "alloc")]
+impl From<&String> for ConstPtr<str> {
+  fn from(text: &String) -> Self {
+    Self::new(text.as_str())
   }
+}
 
-  pub fn access_mut(&mut self) -> &mut T {
-    &mut self.ptr
+/// Nothing can move the pointee through a `ConstPtr`, so the pin needs no further promise
+impl<T: ?Sized> From<Pin<&T>> for ConstPtr<T> {
+  fn from(pin: Pin<&T>) -> Self {
+    Self::new(pin.get_ref())
   }
+}
 
-  #[cfg(test)]
-  pub fn count(&self) -> usize {
-    *self.rc
+/// Bypasses the `RefCell` borrow tracking
+impl<T: ?Sized> From<&RefCell<T>> for ConstPtr<T> {
+  fn from(cell: &RefCell<T>) -> Self {
+    Self(cell.as_ptr().cast_const(), Meta::unregistered())
   }
+}
 
-  fn new_ref_count() -> MutPtr<usize> {
-    MutPtr::new(Box::leak(Box::new(1usize)))
+/// Reads may observe writes made through the cell, which is fine as long as none overlaps a read
+impl<T: ?Sized> From<&Cell<T>> for ConstPtr<T> {
+  fn from(cell: &Cell<T>) -> Self {
+    Self(cell.as_ptr().cast_const(), Meta::unregistered())
   }
 }
 
-impl<T> Default for SmartPtr<T>
-where
-  T: Default,
-{
-  fn default() -> Self {
-    Self::new(T::default())
+impl<T: ?Sized> From<&UnsafeCell<T>> for ConstPtr<T> {
+  fn from(cell: &UnsafeCell<T>) -> Self {
+    Self(cell.get().cast_const(), Meta::unregistered())
   }
 }
 
-impl<T> Drop for SmartPtr<T> {
-  fn drop(&mut self) {
-    if self.valid() {
-      *self.rc -= 1;
-      if *self.rc == 0 {
-        unsafe {
-          let _ = Box::from_raw(self.ptr.raw());
-          let _ = Box::from_raw(self.rc.raw());
-        }
-      }
-    }
+/// `None` becomes a null pointer
+impl<T> From<Option<&T>> for ConstPtr<T> {
+  fn from(value: Option<&T>) -> Self {
+    value.map_or_else(Self::default, Self::new)
   }
 }
 
-impl<T> Deref for SmartPtr<T> {
-  type Target = MutPtr<T>;
-  fn deref(&self) -> &Self::Target {
-    &self.ptr
+/// Accepts null and any pointee including `dyn Trait`, with the `nightly` feature a misaligned address trips a debug
+/// assertion, `try_from_raw` reports both as errors instead
+impl<T: ?Sized> From<*const T> for ConstPtr<T> {
+  #[cfg_attr(debug_assertions, track_caller)]
+  fn from(value: *const T) -> Self {
+    debug_assert_aligned(value, "ConstPtr");
+    Self::from_raw_unchecked(value)
   }
 }
 
-impl<T> DerefMut for SmartPtr<T> {
-  fn deref_mut(&mut self) -> &mut Self::Target {
-    &mut self.ptr
+impl<T: ?Sized> From<ConstPtr<T>> for *const T {
+  fn from(ptr: ConstPtr<T>) -> Self {
+    ptr.0
   }
 }
 
-impl<T> Clone for SmartPtr<T> {
-  fn clone(&self) -> Self {
-    let ptr = self.ptr;
-    let mut rc = self.rc;
+/// Without the `checked` and `borrow-checked` features this is `#[repr(transparent)]` over `*mut T`: same size,
+/// alignment and ABI, so it can sit in `#[repr(C)]` structs and be transmuted from `*mut T` or `Option<&mut T>`, with
+/// null a valid value. Either feature adds bookkeeping next to the address and drops the guarantee.
+#[cfg_attr(not(any(feature = "checked", feature = "borrow-checked")), repr(transparent))]
+pub struct MutPtr<T: ?Sized>(*mut T, Meta);
 
-    *rc += 1;
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+const _: () = {
+  assert!(mem::size_of::<MutPtr<u8>>() == mem::size_of::<*mut u8>());
+  assert!(mem::align_of::<MutPtr<u8>>() == mem::align_of::<*mut u8>());
+  assert!(mem::size_of::<MutPtr<[u8]>>() == mem::size_of::<*mut [u8]>());
+  assert!(mem::size_of::<MutPtr<dyn Any>>() == mem::size_of::<*mut dyn Any>());
+};
 
-    Self { ptr, rc }
+impl<T> Default for MutPtr<T> {
+  fn default() -> Self {
+    Self::null_ptr()
   }
 }
 
-impl<T: Debug> Debug for SmartPtr<T> {
-  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-    self.ptr.fmt(f)
+impl<T> MutPtr<T> {
+  /// Same as `default()`, but usable in `const` items
+  pub const fn null_ptr() -> Self {
+    Self(ptr::null_mut(), Meta::unregistered())
   }
 }
 
-impl<T: Display> Display for SmartPtr<T> {
-  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-    self.ptr.fmt(f)
+impl<T: ?Sized> Clone for MutPtr<T> {
+  fn clone(&self) -> Self {
+    *self
   }
 }
 
-impl<T: PartialEq> PartialEq<SmartPtr<T>> for SmartPtr<T> {
-  fn eq(&self, other: &Self) -> bool {
-    self.ptr.eq(other)
+impl<T: ?Sized> Copy for MutPtr<T> {}
+
+impl<T: ?Sized> MutPtr<T> {
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn new(t: &mut T) -> Self {
+    let ptr: *mut T = t;
+    Self(ptr, Meta::register(ptr.cast_const().cast()))
   }
-}
 
-#[cfg(test)]
-mod tests;
+  /// Like `new`, but with the `borrow-checked` feature every copy checks its borrows at runtime like a `RefCell`:
+  /// `borrow_mut` and mutable dereferences panic while any guard is out, shared ones while a `borrow_mut` guard is
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn tracked(t: &mut T) -> Self {
+    let ptr: *mut T = t;
+    Self(ptr, Meta::register_tracked(ptr.cast_const().cast()))
+  }
+
+  /// A shared borrow that, for a `tracked` pointer, holds off exclusive access until the guard is dropped
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked"), track_caller)]
+  pub fn borrow(&self) -> PtrRef<'_, T> {
+    PtrRef {
+      value: self,
+      _token: self.1.tracking().borrow(),
+    }
+  }
+
+  /// An exclusive borrow that, for a `tracked` pointer, holds off every other access until the guard is dropped
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked"), track_caller)]
+  pub fn borrow_mut(&mut self) -> PtrRefMut<'_, T> {
+    // the same checks as `deref_mut`, with the exclusive check made by taking the token
+    debug_assert!(self.present(), "dereferenced a null MutPtr");
+    self.1.verify("MutPtr");
+    let token = self.1.tracking().borrow_mut();
+    PtrRefMut {
+      value: unsafe { &mut *self.0 },
+      _token: token,
+    }
+  }
+
+  /// Rejects null and misaligned addresses up front, a `TryFrom<*mut T>` is ruled out by the blanket impl that comes with
+  /// `From<*mut T>`
+  pub fn try_from_raw(raw: *mut T) -> Result<Self, PtrError>
+  where
+    T: RawAlign,
+  {
+    if raw.is_null() {
+      Err(PtrError::Null)
+    } else if !T::is_aligned_raw(raw) {
+      Err(PtrError::Misaligned)
+    } else {
+      Ok(Self::from_raw_unchecked(raw))
+    }
+  }
+
+  /// Like `From<*mut T>` without the alignment assertion, for addresses fixed up before they are dereferenced
+  pub const fn from_raw_unchecked(raw: *mut T) -> Self {
+    Self(raw, Meta::unregistered())
+  }
+
+  /// Like `new`, but usable in `const` items, which leaves the pointer out of the `checked` bookkeeping
+  pub const fn from_mut(t: &mut T) -> Self {
+    Self(t, Meta::unregistered())
+  }
+
+  /// Lends `t` to `f` as a pointer. With the `checked` feature in a debug build, any copy smuggled out of `f` panics
+  /// on its next dereference.
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn scoped<R>(t: &mut T, f: impl FnOnce(Self) -> R) -> R {
+    let ptr = Self::new(t);
+    let _scope = Scope(ptr.1);
+    f(ptr)
+  }
+
+  /// Strips the pin, since a `MutPtr` can move the pointee out through `write`, `replace` and friends.
+  ///
+  /// # Safety
+  ///
+  /// The caller takes over the pinning contract, nothing may move the pointee through the returned pointer
+  pub unsafe fn from_pin_mut(pin: Pin<&mut T>) -> Self {
+    Self::new(pin.get_unchecked_mut())
+  }
+
+  pub const fn raw(&self) -> *mut T {
+    self.0
+  }
+
+  pub const fn null(&self) -> bool {
+    self.0.is_null()
+  }
+
+  pub const fn present(&self) -> bool {
+    !self.null()
+  }
+
+  /// Nulls the pointer out, with the `checked` feature every copy made before the call panics on its next dereference
+  pub fn clear(&mut self) {
+    self.1.invalidate();
+    self.0 = self.0.with_addr(0);
+  }
+
+  /// Same as `clear()`, for call sites where retiring the copies is the point
+  pub fn invalidate(&mut self) {
+    self.clear();
+  }
+
+  /// The address without its provenance, for logging and range checks rather than rebuilding a pointer
+  pub fn addr(&self) -> usize {
+    self.0.addr()
+  }
+
+  /// Moves the pointer to `addr` while keeping the provenance of the original
+  pub fn with_addr(self, addr: usize) -> Self {
+    Self(self.0.with_addr(addr), self.1)
+  }
+
+  pub fn map_addr(self, f: impl FnOnce(usize) -> usize) -> Self {
+    Self(self.0.map_addr(f), self.1)
+  }
+
+  /// Like `addr`, but marks the provenance as exposed so `with_exposed_provenance` may rebuild the pointer later
+  pub fn expose_provenance(&self) -> usize {
+    self.0.expose_provenance()
+  }
+
+  /// The preferred way to access the pointee, `None` when the pointer is null
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn as_opt(&self) -> Option<&T> {
+    if self.present() {
+      self.1.verify("MutPtr");
+      Some(unsafe { &*self.0 })
+    } else {
+      None
+    }
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn as_opt_mut(&mut self) -> Option<&mut T> {
+    if self.present() {
+      self.1.verify("MutPtr");
+      Some(unsafe { &mut *self.0 })
+    } else {
+      None
+    }
+  }
+
+  pub fn try_deref(&self) -> Result<&T, PtrError> {
+    self.as_opt().ok_or(PtrError::Null)
+  }
+
+  /// Like `try_deref`, but a pointer the `checked` feature knows to be invalidated is reported as `PtrError::Null`
+  /// instead of panicking
+  pub fn checked_ref(&self) -> Result<&T, PtrError> {
+    if self.present() && self.1.is_current() {
+      self.1.tracking().check_shared();
+      Ok(unsafe { &*self.0 })
+    } else {
+      Err(PtrError::Null)
+    }
+  }
+
+  /// Panics with `msg` if the pointer is null
+  #[track_caller]
+  pub fn expect(&self, msg: &str) -> &T {
+    self.as_opt().expect(msg)
+  }
+
+  /// Like `as_opt`, but the reference is detached from `self`.
+  ///
+  /// # Safety
+  ///
+  /// `'a` is chosen by the caller, nothing stops the reference from outliving the pointee or aliasing other
+  /// references made from copies of this pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub unsafe fn into_opt<'a>(self) -> Option<&'a T> {
+    if self.present() {
+      self.1.verify("MutPtr");
+    }
+    self.0.as_ref()
+  }
+
+  /// # Safety
+  ///
+  /// Same as `into_opt`
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub unsafe fn into_opt_mut<'a>(self) -> Option<&'a mut T> {
+    if self.present() {
+      self.1.verify("MutPtr");
+    }
+    self.0.as_mut()
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn to_option_copied(&self) -> Option<T>
+  where
+    T: Copy,
+  {
+    self.as_opt().copied()
+  }
+
+  /// Calls `f` with the pointee, skipped for a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn map_present<R>(self, f: impl FnOnce(&T) -> R) -> Option<R> {
+    self.as_opt().map(f)
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+    if let Some(value) = self.as_opt() {
+      f(value);
+    }
+    self
+  }
+
+  /// Nulls the pointer out unless the pointee passes `pred`, which is never called for a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn filter(mut self, pred: impl FnOnce(&T) -> bool) -> Self {
+    if !self.as_opt().is_some_and(pred) {
+      // only this copy is nulled, the others still point at a live pointee
+      self.0 = self.0.with_addr(0);
+    }
+    self
+  }
+
+  pub fn or(self, fallback: Self) -> Self {
+    if self.present() {
+      self
+    } else {
+      fallback
+    }
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn with_mut<R>(mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+    self.as_opt_mut().map(f)
+  }
+
+  /// Runs `f` on the pointee, skipped for a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn update(&mut self, f: impl FnOnce(&mut T)) {
+    if let Some(value) = self.as_opt_mut() {
+      f(value);
+    }
+  }
+
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn update_or<R>(&mut self, f: impl FnOnce(&mut T) -> R, default: R) -> R {
+    self.as_opt_mut().map_or(default, f)
+  }
+
+  pub fn try_deref_mut(&mut self) -> Result<&mut T, PtrError> {
+    self.as_opt_mut().ok_or(PtrError::Null)
+  }
+
+  /// Like `try_deref_mut`, but a pointer the `checked` feature knows to be invalidated is reported as `PtrError::Null`
+  /// instead of panicking
+  pub fn checked_mut(&mut self) -> Result<&mut T, PtrError> {
+    if self.present() && self.1.is_current() {
+      self.1.tracking().check_exclusive();
+      Ok(unsafe { &mut *self.0 })
+    } else {
+      Err(PtrError::Null)
+    }
+  }
+
+  /// Panics with `msg` if the pointer is null
+  #[track_caller]
+  pub fn expect_mut(&mut self, msg: &str) -> &mut T {
+    self.as_opt_mut().expect(msg)
+  }
+
+  pub fn cast<U>(self) -> MutPtr<U> {
+    MutPtr(self.0.cast(), self.1)
+  }
+
+  /// Like `cast`, but panics if the resulting pointer is not aligned for `U`
+  pub fn cast_aligned<U>(self) -> MutPtr<U> {
+    let ptr = self.cast::<U>();
+    assert!(ptr.0.is_aligned(), "pointer is not aligned to {} bytes", mem::align_of::<U>());
+    ptr
+  }
+
+  pub fn erase(self) -> MutPtr<()> {
+    self.cast()
+  }
+
+  /// The address as C user data, for handing to callbacks that give it back through `from_void`
+  pub fn into_void(self) -> *mut c_void {
+    self.0.cast()
+  }
+
+  /// Points at a part of the pointee, a null pointer stays null without calling `f`
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn map_mut<U>(mut self, f: impl FnOnce(&mut T) -> &mut U) -> MutPtr<U> {
+    let meta = self.1;
+    match self.as_opt_mut() {
+      Some(value) => MutPtr(f(value), meta),
+      None => MutPtr::default(),
+    }
+  }
+}
+
+/// Arithmetic on null pointers trips a debug assertion in the unchecked variants, the wrapping variants keep them null
+impl<T> MutPtr<T> {
+  /// Rebuilds a pointer from an address previously passed out by `expose_provenance`
+  pub fn with_exposed_provenance(addr: usize) -> Self {
+    Self(ptr::with_exposed_provenance_mut(addr), Meta::unregistered())
+  }
+
+  /// Takes back C user data, a null pointer gives a null `MutPtr`
+  ///
+  /// # Safety
+  ///
+  /// A non-null `data` must point at a `T`, typically it came out of `into_void` on a `MutPtr<T>`.
+  pub unsafe fn from_void(data: *mut c_void) -> Self {
+    Self::from(data.cast::<T>())
+  }
+
+  /// Whether the address is a multiple of `align_of::<T>()`, a null pointer counts as aligned
+  pub fn is_aligned(&self) -> bool {
+    self.0.is_aligned()
+  }
+
+  /// Elements to advance by until the address is a multiple of `align`, same contract as `pointer::align_offset`
+  pub fn align_offset(&self, align: usize) -> usize {
+    self.0.align_offset(align)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::add`
+  pub unsafe fn add(self, count: usize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null MutPtr");
+    Self(self.0.add(count), self.1)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::sub`
+  pub unsafe fn sub(self, count: usize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null MutPtr");
+    Self(self.0.sub(count), self.1)
+  }
+
+  /// # Safety
+  ///
+  /// Same contract as `pointer::offset`
+  pub unsafe fn offset(self, count: isize) -> Self {
+    debug_assert!(self.present(), "pointer arithmetic on a null MutPtr");
+    Self(self.0.offset(count), self.1)
+  }
+
+  /// Distance to `origin` in elements, negative if `origin` comes after `self`
+  ///
+  /// # Safety
+  ///
+  /// Same contract as `pointer::offset_from`
+  pub unsafe fn offset_from(&self, origin: MutPtr<T>) -> isize {
+    self.0.offset_from(origin.0)
+  }
+
+  /// Where `self` sits in `base`, `None` if it points outside the slice or between two elements.
+  ///
+  /// Zero-sized elements all share one address, so any pointer to it counts as index 0.
+  pub fn element_index(&self, base: MutPtr<[T]>) -> Option<usize> {
+    let start = base.raw().cast::<T>().addr();
+    let offset = self.addr().checked_sub(start)?;
+    let index = match mem::size_of::<T>() {
+      0 if offset == 0 => 0,
+      0 => return None,
+      size if offset % size == 0 => offset / size,
+      _ => return None,
+    };
+
+    (base.present() && index < base.len()).then_some(index)
+  }
+
+  pub fn wrapping_add(self, count: usize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_add(count), self.1)
+  }
+
+  pub fn wrapping_sub(self, count: usize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_sub(count), self.1)
+  }
+
+  pub fn wrapping_offset(self, count: isize) -> Self {
+    if self.null() {
+      return self;
+    }
+    Self(self.0.wrapping_offset(count), self.1)
+  }
+
+  /// Overwrites the pointee without dropping the previous value
+  pub fn write(&mut self, value: T) {
+    debug_assert!(self.present(), "write through a null MutPtr");
+    unsafe { ptr::write(self.0, value) }
+  }
+
+  pub fn read(&self) -> T
+  where
+    T: Copy,
+  {
+    debug_assert!(self.present(), "read through a null MutPtr");
+    unsafe { ptr::read(self.0) }
+  }
+
+  /// Moves `value` into the pointee and returns the previous value
+  pub fn replace(&mut self, value: T) -> T {
+    debug_assert!(self.present(), "replace through a null MutPtr");
+    unsafe { ptr::replace(self.0, value) }
+  }
+
+  /// Reads without letting the compiler elide or reorder the access, such as from device registers
+  pub fn read_volatile(&self) -> T
+  where
+    T: Copy,
+  {
+    debug_assert!(self.present(), "volatile read through a null MutPtr");
+    debug_assert!(self.0.is_aligned(), "volatile read through a misaligned MutPtr");
+    unsafe { ptr::read_volatile(self.0) }
+  }
+
+  /// Reads from an address of any alignment, such as fields of packed formats, build the pointer with
+  /// `from_raw_unchecked` to skip the alignment assertion in `From`
+  pub fn read_unaligned(&self) -> T
+  where
+    T: Copy,
+  {
+    debug_assert!(self.present(), "unaligned read through a null MutPtr");
+    unsafe { ptr::read_unaligned(self.0) }
+  }
+
+  /// Writes to an address of any alignment, the previous value is not dropped
+  pub fn write_unaligned(&mut self, value: T) {
+    debug_assert!(self.present(), "unaligned write through a null MutPtr");
+    unsafe { ptr::write_unaligned(self.0, value) }
+  }
+
+  /// The `size_of::<T>()` bytes of the pointee, or an empty null view of a null pointer
+  ///
+  /// # Safety
+  ///
+  /// `T` must have no padding, reading uninitialized padding bytes through the view is undefined behavior. Every write
+  /// must also leave a valid `T` behind, which rules out arbitrary bytes for types such as `bool`, `char`, enums and
+  /// references. With the `bytemuck` feature, `cast_pod_mut::<[u8; N]>` is the safe alternative for `Pod` types.
+  pub unsafe fn as_bytes_mut(&mut self) -> MutPtr<[u8]> {
+    let len = if self.present() { mem::size_of::<T>() } else { 0 };
+    MutPtr(ptr::slice_from_raw_parts_mut(self.0.cast(), len), self.1)
+  }
+
+  /// Writes without letting the compiler elide or reorder the access, the previous value is not dropped
+  pub fn write_volatile(&mut self, value: T) {
+    debug_assert!(self.present(), "volatile write through a null MutPtr");
+    debug_assert!(self.0.is_aligned(), "volatile write through a misaligned MutPtr");
+    unsafe { ptr::write_volatile(self.0, value) }
+  }
+
+  /// Assigns through the pointer, dropping the previous pointee; on a null pointer `value` is dropped instead
+  /// and `false` is returned
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn set(&mut self, value: T) -> bool {
+    match self.as_opt_mut() {
+      Some(slot) => {
+        *slot = value;
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Moves the pointee out and leaves `T::default()` in its place, panics on a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn take(&mut self) -> T
+  where
+    T: Default,
+  {
+    mem::take(self.as_opt_mut().expect("take through a null MutPtr"))
+  }
+
+  /// Like `take`, but only when `pred` accepts the pointee, `None` on a null pointer
+  #[cfg_attr(feature = "checked", track_caller)]
+  pub fn take_if(&mut self, pred: impl FnOnce(&mut T) -> bool) -> Option<T>
+  where
+    T: Default,
+  {
+    let value = self.as_opt_mut()?;
+    if pred(value) {
+      Some(mem::take(value))
+    } else {
+      None
+    }
+  }
+
+  /// Swaps the pointees, the pointers themselves are left untouched
+  pub fn swap(&mut self, other: &mut MutPtr<T>) {
+    debug_assert!(self.present() && other.present(), "swap through a null MutPtr");
+    unsafe { ptr::swap(self.0, other.0) }
+  }
+
+  /// Copies `count` elements from `src` into the elements starting at `self`, the ranges may overlap
+  ///
+  /// # Safety
+  ///
+  /// Same contract as `pointer::copy_from`
+  pub unsafe fn copy_from(&mut self, src: ConstPtr<T>, count: usize) {
+    self.0.copy_from(src.0, count)
+  }
+}
+
+impl MutPtr<[u8]> {
+  /// Reads a `U` starting `offset` bytes in, at any alignment. Panics if it would run past the end.
+  ///
+  /// # Safety
+  ///
+  /// The bytes must form a valid `U`, which holds for plain-old-data such as integers and floats
+  pub unsafe fn read_unaligned_at<U: Copy>(&self, offset: usize) -> U {
+    ConstPtr::from(*self).read_unaligned_at(offset)
+  }
+}
+
+impl<T> MutPtr<[T]> {
+  pub fn from_slice(slice: &mut [T]) -> Self {
+    Self::new(slice)
+  }
+
+  /// Read from the pointer metadata, the slice is never dereferenced
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn get_mut(&mut self, index: usize) -> Option<MutPtr<T>> {
+    if self.present() && index < self.len() {
+      Some(MutPtr(self.0.cast::<T>().wrapping_add(index), self.1))
+    } else {
+      None
+    }
+  }
+
+  /// Borrows the elements, a null pointer yields nothing
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.as_opt().unwrap_or_default().iter()
+  }
+
+  pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+    self.as_opt_mut().unwrap_or_default().iter_mut()
+  }
+
+  /// Assigns every element, dropping the previous values which must therefore be initialized
+  pub fn fill(&mut self, value: T)
+  where
+    T: Clone,
+  {
+    if let Some(slice) = self.as_opt_mut() {
+      slice.fill(value);
+    }
+  }
+
+  pub fn fill_with<F: FnMut() -> T>(&mut self, f: F) {
+    if let Some(slice) = self.as_opt_mut() {
+      slice.fill_with(f);
+    }
+  }
+
+  /// A `memcpy` from `src`, or a `memmove` if the two overlap. Panics if the lengths differ, like `copy_from_slice`.
+  pub fn copy_from_slice_ptr(&mut self, src: ConstPtr<[T]>)
+  where
+    T: Copy,
+  {
+    assert_eq!(
+      self.len(),
+      src.len(),
+      "source slice length does not match destination slice length"
+    );
+    if self.is_empty() {
+      return;
+    }
+
+    let bytes = self.len().saturating_mul(mem::size_of::<T>());
+    let (src, dest) = (src.raw().cast::<T>(), self.raw().cast::<T>());
+    if src.addr().abs_diff(dest.addr()) >= bytes {
+      unsafe { ptr::copy_nonoverlapping(src, dest, self.len()) };
+    } else {
+      unsafe { ptr::copy(src, dest, self.len()) };
+    }
+  }
+
+  /// A `memmove` of the elements in `src` to start at `dest`, with the same panics as `slice::copy_within`
+  pub fn copy_within<R: RangeBounds<usize>>(&mut self, src: R, dest: usize)
+  where
+    T: Copy,
+  {
+    if let Some(slice) = self.as_opt_mut() {
+      slice.copy_within(src, dest);
+    }
+  }
+}
+
+impl<T, const N: usize> MutPtr<[T; N]> {
+  /// The same elements with the length moved into the pointer metadata
+  pub fn as_slice_ptr(&self) -> MutPtr<[T]> {
+    MutPtr(self.0 as *mut [T], self.1)
+  }
+}
+
+impl MutPtr<str> {
+  /// Length in bytes, read from the pointer metadata
+  pub fn len(&self) -> usize {
+    self.as_bytes_ptr().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn as_bytes_ptr(&self) -> ConstPtr<[u8]> {
+    ConstPtr(self.0 as *const [u8], self.1)
+  }
+}
+
+impl MutPtr<dyn Any> {
+  /// `None` for a null pointer rather than dereferencing it
+  pub fn type_id(&self) -> Option<TypeId> {
+    self.as_opt().map(Any::type_id)
+  }
+
+  /// Retypes the pointer if the pointee is a `T`, `None` on a mismatch or a null pointer
+  pub fn downcast_mut<T: Any>(&mut self) -> Option<MutPtr<T>> {
+    if self.as_opt()?.is::<T>() {
+      Some(self.cast())
+    } else {
+      None
+    }
+  }
+}
+
+/// Named `init` rather than `write` because `MutPtr::write` already stores a whole `MaybeUninit<T>`
+impl<T> MutPtr<MaybeUninit<T>> {
+  /// Initializes the pointee and hands back a pointer typed as the initialized value
+  pub fn init(self, value: T) -> MutPtr<T> {
+    debug_assert!(self.present(), "initializing through a null MutPtr");
+    unsafe { (*self.0).write(value) };
+    self.cast()
+  }
+
+  /// # Safety
+  ///
+  /// The pointer must be present and the pointee initialized, same contract as `MaybeUninit::assume_init_mut`
+  pub unsafe fn assume_init_mut(&mut self) -> &mut T {
+    (*self.0).assume_init_mut()
+  }
+}
+
+impl<T> MutPtr<[MaybeUninit<T>]> {
+  /// Copies `src` into the slice and hands back a pointer typed as the initialized elements, panics if the lengths differ
+  pub fn write_slice(self, src: &[T]) -> MutPtr<[T]>
+  where
+    T: Copy,
+  {
+    assert_eq!(self.len(), src.len(), "write_slice between slices of different lengths");
+    if self.present() {
+      unsafe { ptr::copy_nonoverlapping(src.as_ptr(), self.0.cast::<T>(), src.len()) };
+    }
+    MutPtr(self.0 as *mut [T], self.1)
+  }
+}
+
+impl<T: ?Sized> AsRef<T> for MutPtr<T> {
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn as_ref(&self) -> &T {
+    debug_assert!(self.present(), "dereferenced a null MutPtr");
+    self.1.verify("MutPtr");
+    self.1.tracking().check_shared();
+    unsafe { &*self.raw() }
+  }
+}
+
+impl<T: ?Sized> AsMut<T> for MutPtr<T> {
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn as_mut(&mut self) -> &mut T {
+    debug_assert!(self.present(), "dereferenced a null MutPtr");
+    self.1.verify("MutPtr");
+    self.1.tracking().check_exclusive();
+    unsafe { &mut *self.raw() }
+  }
+}
+
+/// Compares by address, not by the pointee's value; all null pointers are equal
+impl<T: ?Sized> PartialEq for MutPtr<T> {
+  fn eq(&self, other: &Self) -> bool {
+    ptr::addr_eq(self.0, other.0)
+  }
+}
+
+impl<T: ?Sized> Eq for MutPtr<T> {}
+
+/// Prints the address and the pointee, or `MutPtr(null)` without dereferencing
+impl<T: ?Sized + Debug> Debug for MutPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self.as_opt() {
+      Some(value) => {
+        write!(f, "MutPtr({:p} -> ", self.0.cast::<()>())?;
+        value.fmt(f)?;
+        write!(f, ")")
+      }
+      None => write!(f, "MutPtr(null)"),
+    }
+  }
+}
+
+/// Forwards to the pointee, or writes `<null>` without dereferencing
+impl<T: ?Sized + Display> Display for MutPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self.as_opt() {
+      Some(value) => value.fmt(f),
+      None => f.pad("<null>"),
+    }
+  }
+}
+
+impl<T: ?Sized> fmt::Pointer for MutPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    fmt::Pointer::fmt(&self.0.cast::<()>(), f)
+  }
+}
+
+/// Orders by address, so null pointers sort first
+impl<T: ?Sized> PartialOrd for MutPtr<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: ?Sized> Ord for MutPtr<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.cast::<()>().cmp(&other.0.cast::<()>())
+  }
+}
+
+/// Hashes the address, consistent with the address-based `PartialEq`
+impl<T: ?Sized> Hash for MutPtr<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.cast::<()>().hash(state);
+  }
+}
+
+impl<T: ?Sized> Deref for MutPtr<T> {
+  type Target = T;
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn deref(&self) -> &Self::Target {
+    debug_assert!(self.present(), "dereferenced a null MutPtr");
+    self.1.verify("MutPtr");
+    self.1.tracking().check_shared();
+    unsafe { &*self.0 }
+  }
+}
+
+impl<T: ?Sized> DerefMut for MutPtr<T> {
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    debug_assert!(self.present(), "dereferenced a null MutPtr");
+    self.1.verify("MutPtr");
+    self.1.tracking().check_exclusive();
+    unsafe { &mut *self.0 }
+  }
+}
+
+impl<T> Index<usize> for MutPtr<[T]> {
+  type Output = T;
+  fn index(&self, index: usize) -> &Self::Output {
+    &self.as_ref()[index]
+  }
+}
+
+impl<T> IndexMut<usize> for MutPtr<[T]> {
+  fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+    &mut self.as_mut()[index]
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MutPtr<[T]> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut MutPtr<[T]> {
+  type Item = &'a mut T;
+  type IntoIter = slice::IterMut<'a, T>;
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<&mut Box<T>> for MutPtr<T> {
+  fn from(ptr: &mut Box<T>) -> Self {
+    Self::new(ptr.as_mut())
+  }
+}
+
+/// Consumes the `Rc` and bypasses the `RefCell` borrow tracking, the pointer dangles unless another `Rc` keeps the
+/// value alive; prefer `From<&Rc<RefCell<T>>>`
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<Rc<RefCell<T>>> for MutPtr<T> {
+  fn from(ptr: Rc<RefCell<T>>) -> Self {
+    Self(ptr.as_ptr(), Meta::unregistered())
+  }
+}
+
+/// Bypasses the `RefCell` borrow tracking
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<&Rc<RefCell<T>>> for MutPtr<T> {
+  fn from(ptr: &Rc<RefCell<T>>) -> Self {
+    Self(ptr.as_ptr(), Meta::unregistered())
+  }
+}
+
+/// Bypasses the `RefCell` borrow tracking, the cell can stay in place as a plain field
+impl<T: ?Sized> From<&RefCell<T>> for MutPtr<T> {
+  fn from(cell: &RefCell<T>) -> Self {
+    Self(cell.as_ptr(), Meta::unregistered())
+  }
+}
+
+/// Writes must respect what `Cell` allows, no reference into the value may be alive while one goes through
+impl<T: ?Sized> From<&Cell<T>> for MutPtr<T> {
+  fn from(cell: &Cell<T>) -> Self {
+    Self(cell.as_ptr(), Meta::unregistered())
+  }
+}
+
+/// Writes must respect the aliasing rules of `UnsafeCell::get`, no other access may overlap them
+impl<T: ?Sized> From<&UnsafeCell<T>> for MutPtr<T> {
+  fn from(cell: &UnsafeCell<T>) -> Self {
+    Self(cell.get(), Meta::unregistered())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<&mut Vec<T>> for MutPtr<[T]> {
+  fn from(items: &mut Vec<T>) -> Self {
+    Self::from_slice(items)
+  }
+}
+
+impl<T> From<&mut MaybeUninit<T>> for MutPtr<MaybeUninit<T>> {
+  fn from(slot: &mut MaybeUninit<T>) -> Self {
+    Self::new(slot)
+  }
+}
+
+impl<T> From<&mut [T]> for MutPtr<[T]> {
+  fn from(slice: &mut [T]) -> Self {
+    Self::from_slice(slice)
+  }
+}
+
+impl<T, const N: usize> From<&mut [T; N]> for MutPtr<[T]> {
+  fn from(array: &mut [T; N]) -> Self {
+    Self::from_slice(array)
+  }
+}
+
+impl From<&mut str> for MutPtr<str> {
+  fn from(text: &mut str) -> Self {
+    Self::new(text)
+  }
+}
+
+/// `None` becomes a null pointer
+impl<T> From<Option<&mut T>> for MutPtr<T> {
+  fn from(value: Option<&mut T>) -> Self {
+    value.map_or_else(Self::default, Self::new)
+  }
+}
+
+/// Accepts null and any pointee including `dyn Trait`, with the `nightly` feature a misaligned address trips a debug
+/// assertion, `try_from_raw` reports both as errors instead
+impl<T: ?Sized> From<*mut T> for MutPtr<T> {
+  #[cfg_attr(debug_assertions, track_caller)]
+  fn from(value: *mut T) -> Self {
+    debug_assert_aligned(value, "MutPtr");
+    Self::from_raw_unchecked(value)
+  }
+}
+
+// a generic `?Sized` pointee only reports its alignment through `align_of_val_raw`, which is still unstable, so on
+// stable this checks nothing and `try_from_raw` is the way to validate
+#[cfg_attr(debug_assertions, track_caller)]
+fn debug_assert_aligned<T: ?Sized>(raw: *const T, kind: &str) {
+  #[cfg(feature = "nightly")]
+  debug_assert!(
+    raw.is_null() || raw.addr() % unsafe { mem::align_of_val_raw(raw) } == 0,
+    "{kind} from a misaligned address"
+  );
+  #[cfg(not(feature = "nightly"))]
+  let _ = (raw, kind);
+}
+
+impl<T: ?Sized> From<MutPtr<T>> for *mut T {
+  fn from(ptr: MutPtr<T>) -> Self {
+    ptr.0
+  }
+}
+
+/// Swaps the values behind two pointers, taking them by value since both are `Copy`
+pub fn swap_pointees<T>(a: MutPtr<T>, b: MutPtr<T>) {
+  debug_assert!(a.present() && b.present(), "swap_pointees on a null MutPtr");
+  debug_assert!(
+    a == b || a.addr().abs_diff(b.addr()) >= mem::size_of::<T>(),
+    "swap_pointees on overlapping values"
+  );
+  unsafe { ptr::swap(a.raw(), b.raw()) }
+}
+
+/// Implemented for every type, named to stay clear of the inherent `as_ptr` on slices, `Vec`, `RefCell` and friends
+pub trait AsPtr {
+  fn const_ptr(&self) -> ConstPtr<Self> {
+    ConstPtr::new(self)
+  }
+
+  fn mut_ptr(&mut self) -> MutPtr<Self> {
+    MutPtr::new(self)
+  }
+}
+
+impl<T: ?Sized> AsPtr for T {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests;
@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn core_pointers_without_std() {
+  let mut value = 1u32;
+
+  let mut mut_ptr = MutPtr::new(&mut value);
+  *mut_ptr = 2;
+  assert_eq!(mut_ptr.as_opt(), Some(&2));
+
+  let const_ptr = ConstPtr::from(mut_ptr);
+  assert_eq!(*const_ptr, 2);
+  assert!(const_ptr == ConstPtr::new(&value));
+
+  let mut null = MutPtr::<u32>::default();
+  assert!(null.null());
+  assert_eq!(null.try_deref_mut(), Err(PtrError::Null));
+
+  let values = [1u8, 2, 3];
+  let slice = ConstPtr::from_slice(&values);
+  assert_eq!(slice.len(), 3);
+  assert_eq!(slice[2], 3);
+
+  assert!(NonNullConstPtr::try_from(ConstPtr::<u8>::default()).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn smart_pointers_with_alloc() {
+  let ptr = SmartPtr::new(5u32);
+  let weak = ptr.downgrade();
+  let ptr_cpy = ptr.clone();
+
+  assert_eq!(ptr.strong_count(), 2);
+  drop(ptr_cpy);
+  assert_eq!(ptr.try_unwrap().ok(), Some(5));
+  assert!(weak.upgrade().is_none());
+}
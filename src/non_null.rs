@@ -0,0 +1,129 @@
+use super::{ConstPtr, Meta, MutPtr, PtrError};
+use core::{
+  ops::{Deref, DerefMut},
+  ptr::NonNull,
+};
+
+pub struct NonNullConstPtr<T: ?Sized>(NonNull<T>);
+
+impl<T: ?Sized> NonNullConstPtr<T> {
+  pub fn new(t: &T) -> Self {
+    Self(NonNull::from(t))
+  }
+
+  pub fn raw(&self) -> *const T {
+    self.0.as_ptr()
+  }
+}
+
+impl<T: ?Sized> AsRef<T> for NonNullConstPtr<T> {
+  fn as_ref(&self) -> &T {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<T: ?Sized> Clone for NonNullConstPtr<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for NonNullConstPtr<T> {}
+
+impl<T: ?Sized> Deref for NonNullConstPtr<T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<T: ?Sized> From<NonNullMutPtr<T>> for NonNullConstPtr<T> {
+  fn from(ptr: NonNullMutPtr<T>) -> Self {
+    Self(ptr.0)
+  }
+}
+
+impl<T: ?Sized> TryFrom<*const T> for NonNullConstPtr<T> {
+  type Error = PtrError;
+  fn try_from(value: *const T) -> Result<Self, Self::Error> {
+    NonNull::new(value.cast_mut()).map(Self).ok_or(PtrError::Null)
+  }
+}
+
+impl<T: ?Sized> TryFrom<ConstPtr<T>> for NonNullConstPtr<T> {
+  type Error = PtrError;
+  fn try_from(value: ConstPtr<T>) -> Result<Self, Self::Error> {
+    Self::try_from(value.raw())
+  }
+}
+
+impl<T: ?Sized> From<NonNullConstPtr<T>> for ConstPtr<T> {
+  fn from(ptr: NonNullConstPtr<T>) -> Self {
+    Self(ptr.raw(), Meta::unregistered())
+  }
+}
+
+pub struct NonNullMutPtr<T: ?Sized>(NonNull<T>);
+
+impl<T: ?Sized> NonNullMutPtr<T> {
+  pub fn new(t: &mut T) -> Self {
+    Self(NonNull::from(t))
+  }
+
+  pub fn raw(&self) -> *mut T {
+    self.0.as_ptr()
+  }
+}
+
+impl<T: ?Sized> AsRef<T> for NonNullMutPtr<T> {
+  fn as_ref(&self) -> &T {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<T: ?Sized> AsMut<T> for NonNullMutPtr<T> {
+  fn as_mut(&mut self) -> &mut T {
+    unsafe { self.0.as_mut() }
+  }
+}
+
+impl<T: ?Sized> Clone for NonNullMutPtr<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for NonNullMutPtr<T> {}
+
+impl<T: ?Sized> Deref for NonNullMutPtr<T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<T: ?Sized> DerefMut for NonNullMutPtr<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    unsafe { self.0.as_mut() }
+  }
+}
+
+impl<T: ?Sized> TryFrom<*mut T> for NonNullMutPtr<T> {
+  type Error = PtrError;
+  fn try_from(value: *mut T) -> Result<Self, Self::Error> {
+    NonNull::new(value).map(Self).ok_or(PtrError::Null)
+  }
+}
+
+impl<T: ?Sized> TryFrom<MutPtr<T>> for NonNullMutPtr<T> {
+  type Error = PtrError;
+  fn try_from(value: MutPtr<T>) -> Result<Self, Self::Error> {
+    Self::try_from(value.raw())
+  }
+}
+
+impl<T: ?Sized> From<NonNullMutPtr<T>> for MutPtr<T> {
+  fn from(ptr: NonNullMutPtr<T>) -> Self {
+    Self(ptr.raw(), Meta::unregistered())
+  }
+}
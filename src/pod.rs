@@ -0,0 +1,74 @@
+//! Reinterpreting plain-old-data through the pointers, validated by `bytemuck`, behind the `bytemuck` feature, plus
+//! `TransparentWrapper` casts between the wrappers and raw pointers while both are `#[repr(transparent)]`
+
+use super::{ConstPtr, MutPtr, PtrError};
+use bytemuck::{AnyBitPattern, NoUninit, Pod, PodCastError};
+use core::mem;
+
+impl From<PodCastError> for PtrError {
+  fn from(err: PodCastError) -> Self {
+    match err {
+      PodCastError::TargetAlignmentGreaterAndInputNotAligned | PodCastError::AlignmentMismatch => Self::Misaligned,
+      PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => Self::SizeMismatch,
+    }
+  }
+}
+
+// same checks `bytemuck::try_cast_ref` makes, without needing a reference to a possibly null pointee
+fn check_cast<T, U>(raw: *const T) -> Result<(), PtrError> {
+  if raw.is_null() {
+    Err(PtrError::Null)
+  } else if mem::size_of::<T>() != mem::size_of::<U>() {
+    Err(PtrError::SizeMismatch)
+  } else if !raw.cast::<U>().is_aligned() {
+    Err(PtrError::Misaligned)
+  } else {
+    Ok(())
+  }
+}
+
+impl<T: NoUninit> ConstPtr<T> {
+  /// Views the pointee as a `U` of the same size, failing on a null or misaligned pointer
+  pub fn cast_pod<U: AnyBitPattern>(self) -> Result<ConstPtr<U>, PtrError> {
+    check_cast::<T, U>(self.0)?;
+    Ok(self.cast())
+  }
+}
+
+impl<T: Pod> MutPtr<T> {
+  /// Views the pointee as a `U` of the same size, failing on a null or misaligned pointer
+  pub fn cast_pod_mut<U: Pod>(self) -> Result<MutPtr<U>, PtrError> {
+    check_cast::<T, U>(self.0)?;
+    Ok(self.cast())
+  }
+}
+
+impl<T: NoUninit> ConstPtr<[T]> {
+  /// Views the elements as `U`s, the length adjusted to cover the same bytes.
+  ///
+  /// Fails on a null or misaligned pointer, or if the bytes do not divide evenly into `U`s.
+  pub fn cast_slice<U: AnyBitPattern>(self) -> Result<ConstPtr<[U]>, PtrError> {
+    let slice = self.as_opt().ok_or(PtrError::Null)?;
+    let cast = bytemuck::try_cast_slice::<T, U>(slice)?;
+    Ok(ConstPtr(cast, self.1))
+  }
+}
+
+impl<T: Pod> MutPtr<[T]> {
+  /// Views the elements as `U`s, the length adjusted to cover the same bytes.
+  ///
+  /// Fails on a null or misaligned pointer, or if the bytes do not divide evenly into `U`s.
+  pub fn cast_slice_mut<U: Pod>(mut self) -> Result<MutPtr<[U]>, PtrError> {
+    let meta = self.1;
+    let slice = self.as_opt_mut().ok_or(PtrError::Null)?;
+    let cast = bytemuck::try_cast_slice_mut::<T, U>(slice)?;
+    Ok(MutPtr(cast, meta))
+  }
+}
+
+// both are `#[repr(transparent)]` over the raw pointer exactly when `Meta` is zero sized
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+unsafe impl<T: ?Sized> bytemuck::TransparentWrapper<*const T> for ConstPtr<T> {}
+
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+unsafe impl<T: ?Sized> bytemuck::TransparentWrapper<*mut T> for MutPtr<T> {}
@@ -0,0 +1,132 @@
+use super::{
+  smart::{RefCount, SmartBox, Source},
+  MutPtr, SmartPtr,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{mem::MaybeUninit, ptr};
+
+const DEFAULT_CHUNK_SIZE: usize = 64;
+
+// the count comes first in a `SmartBox`, so a pointer to it is a pointer to the whole slot
+type Slot<T> = SmartBox<T>;
+
+struct PoolInner<T> {
+  // kept as raw pointers so handing out slots never retags the whole chunk
+  chunks: Vec<*mut [MaybeUninit<Slot<T>>]>,
+  free: Vec<*mut Slot<T>>,
+  chunk_size: usize,
+  live: usize,
+  owned: bool,
+}
+
+impl<T> PoolInner<T> {
+  fn grow(&mut self) {
+    let chunk = Box::into_raw(Box::<[Slot<T>]>::new_uninit_slice(self.chunk_size));
+    self.chunks.push(chunk);
+
+    let first = chunk.cast::<Slot<T>>();
+    // reversed so slots are handed out in address order
+    self.free.extend((0..self.chunk_size).rev().map(|i| unsafe { first.add(i) }));
+  }
+}
+
+impl<T> Drop for PoolInner<T> {
+  fn drop(&mut self) {
+    for chunk in self.chunks.drain(..) {
+      let _ = unsafe { Box::from_raw(chunk) };
+    }
+  }
+}
+
+/// Hands out `SmartPtr`s whose value and reference count share one slot of a pre-allocated chunk, slots go back on a
+/// free list once their last reference is gone
+///
+/// Pointers may outlive the pool, the chunks are only freed once both the pool and every slot are released.
+pub struct SmartPool<T> {
+  inner: MutPtr<PoolInner<T>>,
+}
+
+impl<T> SmartPool<T> {
+  pub fn new() -> Self {
+    Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+  }
+
+  /// Grows by `chunk_size` slots at a time whenever the free list runs out
+  pub fn with_chunk_size(chunk_size: usize) -> Self {
+    assert!(chunk_size > 0, "SmartPool chunk size must be non-zero");
+
+    Self {
+      inner: MutPtr::new(Box::leak(Box::new(PoolInner {
+        chunks: Vec::new(),
+        free: Vec::new(),
+        chunk_size,
+        live: 0,
+        owned: true,
+      }))),
+    }
+  }
+
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn alloc(&self, value: T) -> SmartPtr<T> {
+    let mut inner = self.inner;
+    if inner.free.is_empty() {
+      inner.grow();
+    }
+
+    let slot = inner.free.pop().unwrap();
+    inner.live += 1;
+
+    unsafe {
+      slot.write(Slot {
+        rc: RefCount {
+          strong: 1,
+          weak: 1,
+          source: Source::Pool {
+            pool: inner.raw().cast(),
+            recycle: recycle::<T>,
+          },
+          hook: None,
+        },
+        value: MaybeUninit::new(value),
+      });
+
+      let value = ptr::addr_of_mut!((*slot).value).cast::<T>();
+      SmartPtr::from_parts(MutPtr::from(value), MutPtr::from(slot.cast::<RefCount>()))
+    }
+  }
+
+  /// Slots currently holding a value or referenced by a `WeakPtr`
+  pub fn live(&self) -> usize {
+    self.inner.live
+  }
+
+  /// Slots allocated so far, live or free
+  pub fn capacity(&self) -> usize {
+    self.inner.chunks.len() * self.inner.chunk_size
+  }
+}
+
+impl<T> Default for SmartPool<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Drop for SmartPool<T> {
+  fn drop(&mut self) {
+    self.inner.owned = false;
+    if self.inner.live == 0 {
+      let _ = unsafe { Box::from_raw(self.inner.raw()) };
+    }
+  }
+}
+
+unsafe fn recycle<T>(pool: *mut (), rc: *mut RefCount) {
+  let inner = pool.cast::<PoolInner<T>>();
+  (*inner).free.push(rc.cast());
+  (*inner).live -= 1;
+
+  if !(*inner).owned && (*inner).live == 0 {
+    let _ = Box::from_raw(inner);
+  }
+}
@@ -0,0 +1,107 @@
+use super::{ConstPtr, MutPtr};
+use core::{
+  marker::PhantomData,
+  ops::{Deref, DerefMut},
+  ptr::NonNull,
+};
+
+/// A `ConstPtr` that keeps the lifetime of the reference it came from, so the borrow checker still applies
+pub struct RefPtr<'a, T: ?Sized>(NonNull<T>, PhantomData<&'a T>);
+
+unsafe impl<T: ?Sized + Sync> Send for RefPtr<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RefPtr<'_, T> {}
+
+impl<'a, T: ?Sized> RefPtr<'a, T> {
+  pub fn new(t: &'a T) -> Self {
+    Self(NonNull::from(t), PhantomData)
+  }
+
+  pub fn raw(&self) -> *const T {
+    self.0.as_ptr()
+  }
+
+  pub fn into_ref(self) -> &'a T {
+    unsafe { self.0.as_ref() }
+  }
+
+  /// Drops the lifetime, from here on the caller is responsible for the pointee outliving the pointer
+  pub fn erase(self) -> ConstPtr<T> {
+    ConstPtr::new(self.into_ref())
+  }
+}
+
+impl<T: ?Sized> Clone for RefPtr<'_, T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for RefPtr<'_, T> {}
+
+impl<T: ?Sized> Deref for RefPtr<'_, T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<'a, T: ?Sized> From<&'a T> for RefPtr<'a, T> {
+  fn from(t: &'a T) -> Self {
+    Self::new(t)
+  }
+}
+
+impl<'a, T: ?Sized> From<RefMutPtr<'a, T>> for RefPtr<'a, T> {
+  fn from(ptr: RefMutPtr<'a, T>) -> Self {
+    Self(ptr.0, PhantomData)
+  }
+}
+
+/// A `MutPtr` that keeps the lifetime of the reference it came from, unique like the `&mut` it wraps so it is not `Copy`
+pub struct RefMutPtr<'a, T: ?Sized>(NonNull<T>, PhantomData<&'a mut T>);
+
+unsafe impl<T: ?Sized + Send> Send for RefMutPtr<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RefMutPtr<'_, T> {}
+
+impl<'a, T: ?Sized> RefMutPtr<'a, T> {
+  pub fn new(t: &'a mut T) -> Self {
+    Self(NonNull::from(t), PhantomData)
+  }
+
+  pub fn raw(&self) -> *mut T {
+    self.0.as_ptr()
+  }
+
+  /// A shorter-lived copy, the original is unusable until it is dropped
+  pub fn reborrow(&mut self) -> RefMutPtr<'_, T> {
+    RefMutPtr(self.0, PhantomData)
+  }
+
+  pub fn into_mut(mut self) -> &'a mut T {
+    unsafe { self.0.as_mut() }
+  }
+
+  /// Drops the lifetime, from here on the caller is responsible for the pointee outliving the pointer
+  pub fn erase(self) -> MutPtr<T> {
+    MutPtr::new(self.into_mut())
+  }
+}
+
+impl<T: ?Sized> Deref for RefMutPtr<'_, T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    unsafe { self.0.as_ref() }
+  }
+}
+
+impl<T: ?Sized> DerefMut for RefMutPtr<'_, T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    unsafe { self.0.as_mut() }
+  }
+}
+
+impl<'a, T: ?Sized> From<&'a mut T> for RefMutPtr<'a, T> {
+  fn from(t: &'a mut T) -> Self {
+    Self::new(t)
+  }
+}
@@ -0,0 +1,68 @@
+use super::{ConstPtr, MutPtr};
+use core::ops::{Deref, DerefMut};
+
+/// A `MutPtr` that may be moved to another thread
+pub struct SendPtr<T: ?Sized>(MutPtr<T>);
+
+unsafe impl<T: ?Sized> Send for SendPtr<T> {}
+
+impl<T: ?Sized> SendPtr<T> {
+  /// # Safety
+  ///
+  /// The caller guarantees the pointee outlives every use on the receiving thread and that no other thread
+  /// accesses it while the receiving thread does
+  pub unsafe fn new(ptr: MutPtr<T>) -> Self {
+    Self(ptr)
+  }
+
+  pub fn into_inner(self) -> MutPtr<T> {
+    self.0
+  }
+}
+
+impl<T: ?Sized> Deref for SendPtr<T> {
+  type Target = MutPtr<T>;
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<T: ?Sized> DerefMut for SendPtr<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+/// A `ConstPtr` that may be shared between threads
+pub struct SyncPtr<T: ?Sized>(ConstPtr<T>);
+
+unsafe impl<T: ?Sized> Send for SyncPtr<T> {}
+unsafe impl<T: ?Sized> Sync for SyncPtr<T> {}
+
+impl<T: ?Sized> SyncPtr<T> {
+  /// # Safety
+  ///
+  /// The caller guarantees the pointee outlives every thread holding the pointer and is not mutated while shared
+  pub const unsafe fn new(ptr: ConstPtr<T>) -> Self {
+    Self(ptr)
+  }
+
+  pub const fn into_inner(self) -> ConstPtr<T> {
+    self.0
+  }
+}
+
+impl<T: ?Sized> Clone for SyncPtr<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for SyncPtr<T> {}
+
+impl<T: ?Sized> Deref for SyncPtr<T> {
+  type Target = ConstPtr<T>;
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "alloc")]
+use super::PtrError;
+#[cfg(feature = "alloc")]
+use super::SmartPtr;
+use super::{ConstPtr, MutPtr};
+#[cfg(feature = "leaky-deserialize")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use serde::{de, de::DeserializeSeed, ser};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: ?Sized + Serialize> Serialize for ConstPtr<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.as_opt().serialize(serializer)
+  }
+}
+
+impl<T: ?Sized + Serialize> Serialize for MutPtr<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.as_opt().serialize(serializer)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + Serialize> Serialize for SmartPtr<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self.try_access() {
+      Ok(value) => value.serialize(serializer),
+      Err(err) => Err(ser::Error::custom(err)),
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SmartPtr<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    T::deserialize(deserializer).map(SmartPtr::new)
+  }
+}
+
+/// Deserializes into the allocation behind an existing pointer, so every clone observes the new value
+#[cfg(feature = "alloc")]
+pub struct SmartPtrSeed<'a, T>(pub &'a SmartPtr<T>);
+
+#[cfg(feature = "alloc")]
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for SmartPtrSeed<'_, T> {
+  type Value = ();
+
+  fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    if !self.0.valid() {
+      return Err(de::Error::custom(PtrError::Null));
+    }
+
+    let value = T::deserialize(deserializer)?;
+    let mut ptr = self.0.value_ptr();
+    *ptr = value;
+    Ok(())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SmartPtr<T> {
+  /// Replaces the shared value with a freshly deserialized one, dropping the old value; fails on an empty pointer
+  /// without consuming any input
+  pub fn deserialize_in_place<'de, D: Deserializer<'de>>(&self, deserializer: D) -> Result<(), D::Error>
+  where
+    T: Deserialize<'de>,
+  {
+    SmartPtrSeed(self).deserialize(deserializer)
+  }
+}
+
+/// Allocates the pointee with `Box::leak`, a null value deserializes into a null pointer.
+///
+/// Nothing ever frees the allocation, so this is only meant for long lived object graphs.
+///
+/// ```
+/// use ptr::MutPtr;
+///
+/// let ptr: MutPtr<usize> = serde_json::from_str("1").unwrap();
+/// assert_eq!(*ptr, 1);
+///
+/// // the pointee is never freed unless reclaimed by hand
+/// unsafe { drop(Box::from_raw(ptr.raw())) };
+/// ```
+#[cfg(feature = "leaky-deserialize")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MutPtr<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Ok(
+      Option::<T>::deserialize(deserializer)?
+        .map(|value| MutPtr::new(Box::leak(Box::new(value))))
+        .unwrap_or_default(),
+    )
+  }
+}
+
+/// Same leaking behavior as the `MutPtr` impl
+#[cfg(feature = "leaky-deserialize")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ConstPtr<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    MutPtr::deserialize(deserializer).map(ConstPtr::from)
+  }
+}
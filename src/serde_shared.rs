@@ -0,0 +1,149 @@
+//! Sharing-preserving serialization for `SmartPtr` fields, used through `#[serde(with = "ptr::serde_shared")]`
+//!
+//! Each allocation is written once as `[id, value]`, every later pointer to it as `[id, null]`, and deserializing
+//! hands out clones of a single `SmartPtr` per id. Ids are only meaningful inside one `with_session` call, outside
+//! of one both directions fail. A pointer reachable from its own value is reported as an error rather than recursed
+//! into.
+//!
+//! ```
+//! use ptr::{serde_shared, SmartPtr};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Material {
+//!   #[serde(with = "serde_shared")]
+//!   albedo: SmartPtr<String>,
+//!   #[serde(with = "serde_shared")]
+//!   normal: SmartPtr<String>,
+//! }
+//!
+//! let texture = SmartPtr::new(String::from("bricks"));
+//! let material = Material { albedo: texture.clone(), normal: texture };
+//!
+//! let json = serde_shared::with_session(|| serde_json::to_string(&material)).unwrap();
+//! assert_eq!(json, r#"{"albedo":[0,"bricks"],"normal":[0,null]}"#);
+//!
+//! let material: Material = serde_shared::with_session(|| serde_json::from_str(&json)).unwrap();
+//! assert!(material.albedo.ptr_eq(&material.normal));
+//! ```
+
+use super::SmartPtr;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+  any::Any,
+  cell::RefCell,
+  collections::{HashMap, HashSet},
+};
+
+#[derive(Default)]
+struct Session {
+  next_id: u64,
+  written: HashMap<*const (), u64>,
+  in_progress: HashSet<*const ()>,
+  read: HashMap<u64, Box<dyn Any>>,
+}
+
+std::thread_local! {
+  static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+// ends the session even if `f` unwinds, so the next one starts with fresh ids
+struct SessionGuard;
+
+impl Drop for SessionGuard {
+  fn drop(&mut self) {
+    SESSION.with(|session| session.borrow_mut().take());
+  }
+}
+
+/// Runs `f` with a fresh id table for every shared pointer it serializes or deserializes on this thread.
+///
+/// Nested calls join the outer session.
+pub fn with_session<R>(f: impl FnOnce() -> R) -> R {
+  let nested = SESSION.with(|session| {
+    let mut session = session.borrow_mut();
+    let nested = session.is_some();
+    session.get_or_insert_with(Session::default);
+    nested
+  });
+
+  if nested {
+    return f();
+  }
+
+  let _guard = SessionGuard;
+  f()
+}
+
+fn in_session<R>(f: impl FnOnce(&mut Session) -> R) -> Option<R> {
+  SESSION.with(|session| session.borrow_mut().as_mut().map(f))
+}
+
+pub fn serialize<T, S>(ptr: &SmartPtr<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+  T: ?Sized + Serialize,
+  S: Serializer,
+{
+  let value = ptr.try_access().map_err(ser::Error::custom)?;
+  let addr = ptr.as_const_ptr().raw().cast::<()>();
+
+  let (id, first) = in_session(|session| {
+    if session.in_progress.contains(&addr) {
+      return Err("shared SmartPtr is reachable from its own value");
+    }
+
+    match session.written.get(&addr) {
+      Some(&id) => Ok((id, false)),
+      None => {
+        let id = session.next_id;
+        session.next_id += 1;
+        session.written.insert(addr, id);
+        session.in_progress.insert(addr);
+        Ok((id, true))
+      }
+    }
+  })
+  .ok_or_else(|| ser::Error::custom("shared SmartPtr serialized outside serde_shared::with_session"))?
+  .map_err(ser::Error::custom)?;
+
+  if !first {
+    return (id, None::<&T>).serialize(serializer);
+  }
+
+  let result = (id, Some(value)).serialize(serializer);
+  in_session(|session| session.in_progress.remove(&addr));
+  result
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<SmartPtr<T>, D::Error>
+where
+  T: Deserialize<'de> + 'static,
+  D: Deserializer<'de>,
+{
+  if in_session(|_| ()).is_none() {
+    return Err(de::Error::custom(
+      "shared SmartPtr deserialized outside serde_shared::with_session",
+    ));
+  }
+
+  let (id, value) = <(u64, Option<T>)>::deserialize(deserializer)?;
+
+  in_session(|session| match value {
+    Some(value) => {
+      let ptr = SmartPtr::new(value);
+      match session.read.insert(id, Box::new(ptr.clone())) {
+        Some(_) => Err(de::Error::custom(format_args!("shared SmartPtr id {id} defined twice"))),
+        None => Ok(ptr),
+      }
+    }
+    None => match session.read.get(&id) {
+      Some(ptr) => ptr
+        .downcast_ref::<SmartPtr<T>>()
+        .cloned()
+        .ok_or_else(|| de::Error::custom(format_args!("shared SmartPtr id {id} refers to a different type"))),
+      None => Err(de::Error::custom(format_args!(
+        "shared SmartPtr id {id} is referenced before its value"
+      ))),
+    },
+  })
+  .unwrap_or_else(|| Err(de::Error::custom("serde_shared session ended while deserializing")))
+}
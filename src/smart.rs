@@ -0,0 +1,941 @@
+#[cfg(feature = "backtrace")]
+use super::diagnostics;
+#[cfg(feature = "stats")]
+use super::stats;
+use super::{ConstPtr, MutPtr, PtrError};
+use alloc::{alloc::handle_alloc_error, boxed::Box, string::String, vec::Vec};
+use core::{
+  alloc::{GlobalAlloc, Layout},
+  borrow::{Borrow, BorrowMut},
+  cmp::Ordering,
+  fmt::{self, Debug, Display, Error, Formatter},
+  hash::{Hash, Hasher},
+  mem::{self, ManuallyDrop, MaybeUninit},
+  ops::{Deref, DerefMut},
+  pin::Pin,
+  ptr,
+};
+
+pub(crate) struct RefCount {
+  pub(crate) strong: usize,
+  pub(crate) weak: usize,
+  pub(crate) source: Source,
+  /// Runs once the last strong reference is dropped, right before the value is
+  pub(crate) hook: Option<Box<dyn FnOnce()>>,
+}
+
+/// Where the value and the count came from, and so where they go back to
+pub(crate) enum Source {
+  /// Both were boxed separately
+  Global,
+  /// Both share one `SmartBox`, `free` releases it without dropping the value
+  Inline(unsafe fn(*mut RefCount)),
+  Alloc(&'static dyn GlobalAlloc),
+  /// Both live in a pool slot, `recycle` hands the slot back once the count is released
+  Pool {
+    pool: *mut (),
+    recycle: unsafe fn(*mut (), *mut RefCount),
+  },
+}
+
+impl RefCount {
+  // checked in release builds too, a wrapped count would free the value while references remain
+  fn retain_strong(&mut self) {
+    self.strong = self.strong.checked_add(1).expect("SmartPtr strong count overflowed");
+  }
+
+  fn retain_weak(&mut self) {
+    self.weak = self.weak.checked_add(1).expect("SmartPtr weak count overflowed");
+  }
+
+  fn release_weak(mut rc: MutPtr<RefCount>) {
+    rc.weak -= 1;
+    if rc.weak == 0 {
+      unsafe {
+        match rc.source {
+          Source::Global => {
+            let _ = Box::from_raw(rc.raw());
+          }
+          Source::Inline(free) => free(rc.raw()),
+          Source::Alloc(alloc) => alloc.dealloc(rc.raw().cast(), Layout::new::<RefCount>()),
+          Source::Pool { pool, recycle } => recycle(pool, rc.raw()),
+        }
+      }
+    }
+  }
+
+  /// Every allocation is still returned if the destructor panics, the panic then carries on to the caller.
+  ///
+  /// # Safety
+  ///
+  /// `value` must be the live value owned by `rc`, it is dropped and its memory handed back to where it came from
+  unsafe fn release_value<T: ?Sized>(rc: MutPtr<RefCount>, value: *mut T) {
+    // frees the memory once the destructor is done, whether it returned or unwound
+    struct Dealloc {
+      alloc: &'static dyn GlobalAlloc,
+      ptr: *mut u8,
+      layout: Layout,
+    }
+
+    impl Drop for Dealloc {
+      fn drop(&mut self) {
+        if self.layout.size() != 0 {
+          unsafe { self.alloc.dealloc(self.ptr, self.layout) };
+        }
+      }
+    }
+
+    match rc.source {
+      // the box frees its memory even when the value's destructor unwinds
+      Source::Global => {
+        let _ = Box::from_raw(value);
+      }
+      Source::Alloc(alloc) => {
+        let _dealloc = Dealloc {
+          alloc,
+          ptr: value.cast(),
+          layout: Layout::for_value(&*value),
+        };
+        ptr::drop_in_place(value);
+      }
+      // the block itself is released along with the count
+      Source::Inline(_) | Source::Pool { .. } => ptr::drop_in_place(value),
+    }
+  }
+}
+
+/// The count followed by the value in a single allocation, like the block behind an `Rc`
+#[repr(C)]
+pub(crate) struct SmartBox<T> {
+  pub(crate) rc: RefCount,
+  pub(crate) value: MaybeUninit<T>,
+}
+
+impl<T> SmartBox<T> {
+  /// Allocates a block with an uninitialized value, counting `strong` references and the weak one they share
+  fn alloc(strong: usize) -> (MutPtr<T>, MutPtr<RefCount>) {
+    let block = Box::into_raw(Box::<Self>::new_uninit()).cast::<Self>();
+
+    unsafe {
+      ptr::addr_of_mut!((*block).rc).write(RefCount {
+        strong,
+        weak: 1,
+        source: Source::Inline(Self::free),
+        hook: None,
+      });
+
+      let value = ptr::addr_of_mut!((*block).value).cast::<T>();
+      (MutPtr::from(value), MutPtr::from(block.cast::<RefCount>()))
+    }
+  }
+
+  unsafe fn free(rc: *mut RefCount) {
+    let _ = Box::from_raw(rc.cast::<Self>());
+  }
+}
+
+fn alloc_in<U>(alloc: &dyn GlobalAlloc, value: U) -> MutPtr<U> {
+  let layout = Layout::new::<U>();
+  let raw = if layout.size() == 0 {
+    ptr::dangling_mut::<U>()
+  } else {
+    unsafe { alloc.alloc(layout).cast::<U>() }
+  };
+
+  if raw.is_null() {
+    handle_alloc_error(layout);
+  }
+
+  unsafe { raw.write(value) };
+
+  MutPtr::from(raw)
+}
+
+/// Holds both the value pointer and the count pointer, so unlike `ConstPtr` and `MutPtr` it has no layout guarantee
+/// and is not FFI safe, pass `as_mut_ptr` or `into_raw` across instead
+pub struct SmartPtr<T: ?Sized> {
+  ptr: MutPtr<T>,
+  rc: MutPtr<RefCount>,
+}
+
+impl<T> SmartPtr<T> {
+  /// Allocates the value and its reference count together in one block, a zero-sized value takes no space in it
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn new(item: T) -> Self {
+    let (mut ptr, rc) = SmartBox::<T>::alloc(1);
+    ptr.write(item);
+
+    Self { ptr, rc }.tracked()
+  }
+
+  /// A placeholder holding no value and no reference count, nothing is allocated until `set` or `get_or_init`
+  pub fn empty() -> Self {
+    Self {
+      ptr: MutPtr::default(),
+      rc: MutPtr::default(),
+    }
+  }
+
+  /// Repoints `self` at a fresh allocation of `value`, releasing the reference it held before
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn set(&mut self, value: T) {
+    *self = Self::new(value);
+  }
+
+  /// Fills an empty pointer with the result of `f`, otherwise leaves the shared value alone
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn get_or_init<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+    if self.is_empty() {
+      self.set(f());
+    }
+
+    self.access_mut()
+  }
+
+  /// Hands `f` a weak pointer to the allocation the value is about to move into, like `Rc::new_cyclic`.
+  ///
+  /// Upgrading it fails until `f` has returned.
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn new_cyclic(f: impl FnOnce(&WeakPtr<T>) -> T) -> Self {
+    // if `f` unwinds, dropping `weak` frees the block without touching the missing value
+    let (mut ptr, mut rc) = SmartBox::<T>::alloc(0);
+
+    let weak = WeakPtr { ptr, rc };
+    let value = f(&weak);
+    mem::forget(weak);
+
+    ptr.write(value);
+    rc.strong = 1;
+
+    Self { ptr, rc }.tracked()
+  }
+
+  /// Runs `hook` on the value right before the last strong reference drops it.
+  ///
+  /// It runs exactly once, or never if the value is moved out with `try_unwrap` or the pointer is leaked.
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn with_drop_hook(value: T, hook: impl FnOnce(&mut T) + 'static) -> Self
+  where
+    T: 'static,
+  {
+    let mut this = Self::new(value);
+    let mut ptr = this.ptr;
+    this.rc.hook = Some(Box::new(move || hook(&mut ptr)));
+
+    this
+  }
+
+  /// Allocates room for the value without constructing it, dropping the result never drops a `T`
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn new_uninit() -> SmartPtr<MaybeUninit<T>> {
+    let (ptr, rc) = SmartBox::<MaybeUninit<T>>::alloc(1);
+
+    SmartPtr { ptr, rc }.tracked()
+  }
+
+  /// Builds the value directly in its heap allocation instead of moving it there from the stack
+  ///
+  /// # Safety
+  ///
+  /// `f` must fully initialize the slot it is handed
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub unsafe fn emplace(f: impl FnOnce(&mut MaybeUninit<T>)) -> Self {
+    let mut ptr = Self::new_uninit();
+    f(ptr.access_mut());
+    ptr.assume_init()
+  }
+
+  /// Allocates both the value and its reference count from `alloc`, which also frees them once the last reference is gone
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn new_in(item: T, alloc: &'static dyn GlobalAlloc) -> Self {
+    let ptr = alloc_in(alloc, item);
+    let rc = alloc_in(
+      alloc,
+      RefCount {
+        strong: 1,
+        weak: 1,
+        source: Source::Alloc(alloc),
+        hook: None,
+      },
+    );
+
+    Self { ptr, rc }.tracked()
+  }
+
+  /// Pinning is sound because the value lives in a heap allocation which is never relocated before it is dropped
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn pin(item: T) -> Pin<Self> {
+    unsafe { Pin::new_unchecked(Self::new(item)) }
+  }
+
+  /// Moves the value out if this is the only strong reference, otherwise hands the pointer back
+  pub fn try_unwrap(self) -> Result<T, Self> {
+    if self.strong_count() != 1 {
+      return Err(self);
+    }
+
+    let mut this = ManuallyDrop::new(self);
+    this.rc.hook = None;
+    this.untracked();
+    let value = unsafe { ptr::read(this.ptr.raw()) };
+
+    unsafe {
+      RefCount::release_value(this.rc, this.ptr.raw().cast::<ManuallyDrop<T>>());
+    }
+
+    this.rc.strong = 0;
+    RefCount::release_weak(this.rc);
+
+    Ok(value)
+  }
+
+  pub fn into_inner(self) -> Option<T> {
+    self.try_unwrap().ok()
+  }
+
+  /// Gives up this strong reference without ever releasing it, so the value lives for the rest of the program.
+  ///
+  /// Other clones and weak pointers keep working, but the count never drops below the leaked reference so none of
+  /// them frees the value either. That is also why this hands back a `MutPtr` that never dangles rather than a
+  /// `&'static mut T`, which would alias every clone's `access()`. Panics if the pointer holds no value.
+  #[track_caller]
+  pub fn leak(self) -> MutPtr<T> {
+    assert!(self.valid(), "leaking a SmartPtr without a value");
+
+    let ptr = self.ptr;
+    mem::forget(self);
+    ptr
+  }
+
+  /// Clone on write, repoints `self` at a fresh copy of the value unless it is already unique
+  ///
+  /// # Panics
+  ///
+  /// If the pointer holds no value, check `valid` first where that can happen.
+  #[track_caller]
+  pub fn make_mut(&mut self) -> &mut T
+  where
+    T: Clone,
+  {
+    assert!(self.valid(), "make_mut on a SmartPtr without a value");
+
+    if self.get_mut().is_none() {
+      *self = Self::new(self.access().clone());
+    }
+
+    self.access_mut()
+  }
+}
+
+impl<T: ?Sized> SmartPtr<T> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn from_box(item: Box<T>) -> Self {
+    let ptr = MutPtr::new(Box::leak(item));
+    let rc = Self::new_ref_count();
+
+    Self { ptr, rc }.tracked()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ptr.null() && self.rc.null()
+  }
+
+  /// Whether a strong reference still keeps the value alive.
+  ///
+  /// Zero-sized values sit at a dangling but aligned address, so for them the count alone decides.
+  pub fn valid(&self) -> bool {
+    self.ptr.present() && self.rc.as_opt().is_some_and(|rc| rc.strong > 0)
+  }
+
+  /// Panics if the pointer holds no value
+  pub fn access(&self) -> &T {
+    assert!(self.ptr.present(), "accessing a SmartPtr without a value");
+    // reborrowed straight from the raw pointer, so every clone's references stay children of the allocation's tag
+    unsafe { &*self.ptr.raw() }
+  }
+
+  /// Panics if the pointer holds no value
+  pub fn access_mut(&mut self) -> &mut T {
+    assert!(self.ptr.present(), "accessing a SmartPtr without a value");
+    unsafe { &mut *self.ptr.raw() }
+  }
+
+  /// Copies out the inner pointer, which is not counted as a reference and dangles once the value is dropped
+  pub fn as_const_ptr(&self) -> ConstPtr<T> {
+    self.ptr.into()
+  }
+
+  /// Copies out the inner pointer, which is not counted as a reference and dangles once the value is dropped.
+  ///
+  /// Takes `&mut self` so clones can't hand out aliasing mutable views through shared borrows.
+  pub fn as_mut_ptr(&mut self) -> MutPtr<T> {
+    self.ptr
+  }
+
+  /// Mutable access only while no other strong or weak pointer could observe the value, like `Rc::get_mut`
+  pub fn get_mut(&mut self) -> Option<&mut T> {
+    if self.is_unique() {
+      Some(self.access_mut())
+    } else {
+      None
+    }
+  }
+
+  /// The pointee is never relocated by the pointer itself, but safe methods on any clone can still move it out
+  ///
+  /// # Safety
+  ///
+  /// The caller must not move the value out afterwards, including through `access_mut`, `try_unwrap` or `into_inner`
+  /// on any clone
+  pub unsafe fn as_pin_ref(&self) -> Pin<&T> {
+    Pin::new_unchecked(self.access())
+  }
+
+  /// # Safety
+  ///
+  /// The caller must not move the value out afterwards, including through `access_mut` on any clone
+  pub unsafe fn as_pin_mut(&mut self) -> Pin<&mut T> {
+    Pin::new_unchecked(self.access_mut())
+  }
+
+  pub fn try_access(&self) -> Result<&T, PtrError> {
+    if self.valid() {
+      Ok(self.access())
+    } else {
+      Err(PtrError::Null)
+    }
+  }
+
+  pub fn downgrade(&self) -> WeakPtr<T> {
+    let ptr = self.ptr;
+    let mut rc = self.rc;
+
+    if let Some(rc) = rc.as_opt_mut() {
+      rc.retain_weak();
+    }
+
+    WeakPtr { ptr, rc }
+  }
+
+  /// Gives up the wrapper without touching the reference count, for passing through FFI as an opaque pointer.
+  ///
+  /// Every call must be paired with exactly one `from_raw`, otherwise the reference is leaked.
+  pub fn into_raw(self) -> *mut SmartPtrRepr<T> {
+    let this = ManuallyDrop::new(self);
+    let repr = SmartPtrRepr {
+      ptr: this.ptr,
+      rc: this.rc,
+    };
+
+    Box::into_raw(Box::new(repr))
+  }
+
+  /// Reassembles a pointer produced by `into_raw`, taking back the reference it held.
+  ///
+  /// # Safety
+  ///
+  /// `raw` must come from `into_raw` and must not have been passed to `from_raw` before.
+  pub unsafe fn from_raw(raw: *mut SmartPtrRepr<T>) -> Self {
+    let repr = Box::from_raw(raw);
+
+    Self {
+      ptr: repr.ptr,
+      rc: repr.rc,
+    }
+  }
+
+  /// Points at a part of the value, holding a strong reference so the whole value outlives the projection.
+  ///
+  /// Panics if the pointer holds no value.
+  pub fn project<U: ?Sized>(&self, f: impl FnOnce(&mut T) -> &mut U) -> SmartProjection<T, U> {
+    assert!(self.valid(), "projecting a SmartPtr without a value");
+
+    let parent = self.clone();
+    let ptr = MutPtr::new(f(unsafe { &mut *self.ptr.raw() }));
+
+    SmartProjection { parent, ptr }
+  }
+
+  // for in-place writes that every clone is meant to observe, such as `deserialize_in_place`
+  #[cfg(feature = "serde")]
+  pub(crate) fn value_ptr(&self) -> MutPtr<T> {
+    self.ptr
+  }
+
+  /// Takes over one strong reference counted by `rc`
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub(crate) fn from_parts(ptr: MutPtr<T>, rc: MutPtr<RefCount>) -> Self {
+    Self { ptr, rc }.tracked()
+  }
+
+  // counts a fresh allocation for `stats` and remembers the caller of the constructor for `diagnostics::live_origins`
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn tracked(self) -> Self {
+    #[cfg(feature = "stats")]
+    stats::record_alloc();
+    #[cfg(feature = "backtrace")]
+    diagnostics::record(self.rc, core::panic::Location::caller());
+    self
+  }
+
+  // undoes `tracked` once the last strong reference is gone
+  fn untracked(&self) {
+    #[cfg(feature = "stats")]
+    stats::record_release();
+    #[cfg(feature = "backtrace")]
+    diagnostics::forget(self.rc);
+  }
+
+  /// Where the allocation was made, `None` once the value is gone
+  #[cfg(feature = "backtrace")]
+  pub fn origin(&self) -> Option<&'static core::panic::Location<'static>> {
+    if self.valid() {
+      diagnostics::origin(self.rc)
+    } else {
+      None
+    }
+  }
+
+  /// Whether both pointers share the same allocation, regardless of the values they hold
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    self.ptr == other.ptr
+  }
+
+  pub fn strong_count(&self) -> usize {
+    if self.valid() {
+      self.rc.strong
+    } else {
+      0
+    }
+  }
+
+  /// Whether this is the only pointer to the value, weak pointers included since one could upgrade and observe a
+  /// mutation, the same condition as `Rc::get_mut`
+  pub fn is_unique(&self) -> bool {
+    self.strong_count() == 1 && self.weak_count() == 0
+  }
+
+  /// The `WeakPtr`s to the allocation, not counting the weak reference the strong ones share
+  pub fn weak_count(&self) -> usize {
+    if self.valid() {
+      self.rc.weak - 1
+    } else {
+      0
+    }
+  }
+
+  #[cfg(all(test, feature = "std"))]
+  pub(crate) fn set_strong_count(&mut self, count: usize) {
+    self.rc.strong = count;
+  }
+
+  // the strong references collectively hold one weak reference, released once the value is dropped
+  fn new_ref_count() -> MutPtr<RefCount> {
+    MutPtr::new(Box::leak(Box::new(RefCount {
+      strong: 1,
+      weak: 1,
+      source: Source::Global,
+      hook: None,
+    })))
+  }
+}
+
+impl<T> SmartPtr<[T]> {
+  /// Shrinks `items` into a boxed slice and shares it, every element is dropped once the last reference is gone
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  pub fn from_vec(items: Vec<T>) -> Self {
+    Self::from_box(items.into_boxed_slice())
+  }
+}
+
+impl<T> SmartPtr<MaybeUninit<T>> {
+  /// Retypes the pointer without touching the reference count, other clones keep seeing `MaybeUninit<T>`
+  ///
+  /// # Safety
+  ///
+  /// The value must have been initialized, same contract as `MaybeUninit::assume_init`
+  pub unsafe fn assume_init(self) -> SmartPtr<T> {
+    let this = ManuallyDrop::new(self);
+
+    SmartPtr {
+      ptr: this.ptr.cast(),
+      rc: this.rc,
+    }
+  }
+}
+
+/// Opaque handle produced by `SmartPtr::into_raw`
+pub struct SmartPtrRepr<T: ?Sized> {
+  ptr: MutPtr<T>,
+  rc: MutPtr<RefCount>,
+}
+
+impl<T> Default for SmartPtr<T>
+where
+  T: Default,
+{
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
+
+impl<T> From<T> for SmartPtr<T> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn from(item: T) -> Self {
+    Self::new(item)
+  }
+}
+
+impl<T: ?Sized> From<Box<T>> for SmartPtr<T> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn from(item: Box<T>) -> Self {
+    Self::from_box(item)
+  }
+}
+
+impl<T> From<Vec<T>> for SmartPtr<[T]> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn from(items: Vec<T>) -> Self {
+    Self::from_vec(items)
+  }
+}
+
+impl From<String> for SmartPtr<str> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn from(text: String) -> Self {
+    Self::from_box(text.into_boxed_str())
+  }
+}
+
+/// Copies `text` into a fresh allocation
+impl From<&str> for SmartPtr<str> {
+  #[cfg_attr(feature = "backtrace", track_caller)]
+  fn from(text: &str) -> Self {
+    Self::from_box(Box::from(text))
+  }
+}
+
+/// A view that does not count as a reference, see `SmartPtr::as_const_ptr`
+impl<T: ?Sized> From<&SmartPtr<T>> for ConstPtr<T> {
+  fn from(ptr: &SmartPtr<T>) -> Self {
+    ptr.as_const_ptr()
+  }
+}
+
+/// A view that does not count as a reference, see `SmartPtr::as_mut_ptr`
+impl<T: ?Sized> From<&mut SmartPtr<T>> for MutPtr<T> {
+  fn from(ptr: &mut SmartPtr<T>) -> Self {
+    ptr.as_mut_ptr()
+  }
+}
+
+impl<T: ?Sized> Drop for SmartPtr<T> {
+  fn drop(&mut self) {
+    if self.valid() {
+      self.rc.strong -= 1;
+      if self.rc.strong == 0 {
+        let _weak = ReleaseWeak(self.rc);
+        let _value = ReleaseValue(self.rc, self.ptr.raw());
+        self.untracked();
+        if let Some(hook) = self.rc.hook.take() {
+          hook();
+        }
+      }
+    }
+  }
+}
+
+// gives up the weak reference the strong ones shared even if dropping the value unwinds
+struct ReleaseWeak(MutPtr<RefCount>);
+
+impl Drop for ReleaseWeak {
+  fn drop(&mut self) {
+    RefCount::release_weak(self.0);
+  }
+}
+
+// drops and frees the value once the drop hook is done, whether it returned or unwound
+struct ReleaseValue<T: ?Sized>(MutPtr<RefCount>, *mut T);
+
+impl<T: ?Sized> Drop for ReleaseValue<T> {
+  fn drop(&mut self) {
+    unsafe {
+      RefCount::release_value(self.0, self.1);
+    }
+  }
+}
+
+impl<T: ?Sized> Deref for SmartPtr<T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    self.access()
+  }
+}
+
+impl<T: ?Sized> DerefMut for SmartPtr<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.access_mut()
+  }
+}
+
+/// Lets maps keyed by `SmartPtr<T>` be queried with `&T`, which the value-based `Hash` and `Eq` keep consistent
+impl<T: ?Sized> Borrow<T> for SmartPtr<T> {
+  fn borrow(&self) -> &T {
+    self.access()
+  }
+}
+
+impl<T: ?Sized> BorrowMut<T> for SmartPtr<T> {
+  fn borrow_mut(&mut self) -> &mut T {
+    self.access_mut()
+  }
+}
+
+impl Borrow<str> for SmartPtr<String> {
+  fn borrow(&self) -> &str {
+    self.access()
+  }
+}
+
+impl<T> Borrow<[T]> for SmartPtr<Vec<T>> {
+  fn borrow(&self) -> &[T] {
+    self.access()
+  }
+}
+
+impl<T: ?Sized> Clone for SmartPtr<T> {
+  /// Panics if the strong count would overflow, cloning a pointer without a count just copies it
+  fn clone(&self) -> Self {
+    let ptr = self.ptr;
+    let mut rc = self.rc;
+
+    if let Some(rc) = rc.as_opt_mut() {
+      rc.retain_strong();
+    }
+
+    Self { ptr, rc }
+  }
+}
+
+/// Forwards to the pointee, or writes `SmartPtr(null)` for an empty or invalid pointer.
+///
+/// The alternate form `{:#?}` also shows the allocation address and strong count, for chasing leaks.
+impl<T: ?Sized + Debug> Debug for SmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    if f.alternate() {
+      let mut info = f.debug_struct("SmartPtr");
+      info.field("addr", &format_args!("{:p}", self.ptr.raw().cast::<()>()));
+      info.field("strong", &self.strong_count());
+      match self.try_access() {
+        Ok(value) => info.field("value", &value),
+        Err(_) => info.field("value", &format_args!("<invalid>")),
+      };
+      return info.finish();
+    }
+
+    match self.try_access() {
+      Ok(value) => value.fmt(f),
+      Err(_) => write!(f, "SmartPtr(null)"),
+    }
+  }
+}
+
+/// Forwards to the pointee, or writes `<null>` for an empty or invalid pointer
+impl<T: ?Sized + Display> Display for SmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    match self.try_access() {
+      Ok(value) => value.fmt(f),
+      Err(_) => f.pad("<null>"),
+    }
+  }
+}
+
+impl<T: ?Sized> fmt::Pointer for SmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    fmt::Pointer::fmt(&self.ptr, f)
+  }
+}
+
+/// Compares the pointees, two pointers without a value are equal and never equal to one with a value
+impl<T: ?Sized + PartialEq> PartialEq<SmartPtr<T>> for SmartPtr<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.try_access().ok() == other.try_access().ok()
+  }
+}
+
+impl<T: ?Sized + Eq> Eq for SmartPtr<T> {}
+
+/// Hashes the pointee exactly as `T` would, pointers without a value all hash a fixed sentinel instead
+impl<T: ?Sized + Hash> Hash for SmartPtr<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    match self.try_access() {
+      Ok(value) => value.hash(state),
+      Err(_) => state.write_usize(0),
+    }
+  }
+}
+
+/// Orders by the pointees, pointers without a value sort before every pointer with one
+impl<T: ?Sized + PartialOrd> PartialOrd for SmartPtr<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.try_access().ok().partial_cmp(&other.try_access().ok())
+  }
+}
+
+impl<T: ?Sized + Ord> Ord for SmartPtr<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.try_access().ok().cmp(&other.try_access().ok())
+  }
+}
+
+/// Compares the pointee against `other`, an empty or invalid pointer is never equal to a value
+impl<T: PartialEq> PartialEq<T> for SmartPtr<T> {
+  fn eq(&self, other: &T) -> bool {
+    self.try_access().is_ok_and(|value| value == other)
+  }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq<&T> for SmartPtr<T> {
+  fn eq(&self, other: &&T) -> bool {
+    self.try_access().is_ok_and(|value| value == *other)
+  }
+}
+
+// a blanket `impl<T> PartialEq<SmartPtr<T>> for T` is ruled out by the orphan rules, so only the primitives get the reverse direction
+macro_rules! impl_value_eq_smart_ptr {
+  ($($ty:ty),*) => {
+    $(
+      impl PartialEq<SmartPtr<$ty>> for $ty {
+        fn eq(&self, other: &SmartPtr<$ty>) -> bool {
+          other == self
+        }
+      }
+    )*
+  };
+}
+
+impl_value_eq_smart_ptr!(bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// A pointer into part of a shared value, produced by `SmartPtr::project`
+pub struct SmartProjection<T: ?Sized, U: ?Sized> {
+  parent: SmartPtr<T>,
+  ptr: MutPtr<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> SmartProjection<T, U> {
+  pub fn parent(&self) -> &SmartPtr<T> {
+    &self.parent
+  }
+
+  pub fn access(&self) -> &U {
+    unsafe { &*self.ptr.raw() }
+  }
+
+  pub fn access_mut(&mut self) -> &mut U {
+    unsafe { &mut *self.ptr.raw() }
+  }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for SmartProjection<T, U> {
+  type Target = U;
+  fn deref(&self) -> &Self::Target {
+    self.access()
+  }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for SmartProjection<T, U> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.access_mut()
+  }
+}
+
+impl<T: ?Sized, U: ?Sized> Clone for SmartProjection<T, U> {
+  fn clone(&self) -> Self {
+    Self {
+      parent: self.parent.clone(),
+      ptr: self.ptr,
+    }
+  }
+}
+
+impl<T: ?Sized, U: ?Sized + Debug> Debug for SmartProjection<T, U> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    self.access().fmt(f)
+  }
+}
+
+impl<T: ?Sized, U: ?Sized + Display> Display for SmartProjection<T, U> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    self.access().fmt(f)
+  }
+}
+
+pub struct WeakPtr<T: ?Sized> {
+  ptr: MutPtr<T>,
+  rc: MutPtr<RefCount>,
+}
+
+impl<T> WeakPtr<T> {
+  pub fn new() -> Self {
+    Self {
+      ptr: MutPtr::default(),
+      rc: MutPtr::default(),
+    }
+  }
+}
+
+impl<T: ?Sized> WeakPtr<T> {
+  /// Readable after the value is gone, since the count outlives it for as long as any weak pointer does
+  pub fn strong_count(&self) -> usize {
+    self.rc.as_opt().map_or(0, |rc| rc.strong)
+  }
+
+  /// Like `Weak::weak_count`, zero once no strong reference remains
+  pub fn weak_count(&self) -> usize {
+    match self.rc.as_opt() {
+      Some(rc) if rc.strong > 0 => rc.weak - 1,
+      _ => 0,
+    }
+  }
+
+  pub fn upgrade(&self) -> Option<SmartPtr<T>> {
+    if self.ptr.present() && self.rc.as_opt().is_some_and(|rc| rc.strong > 0) {
+      let ptr = self.ptr;
+      let mut rc = self.rc;
+
+      rc.retain_strong();
+
+      Some(SmartPtr { ptr, rc })
+    } else {
+      None
+    }
+  }
+}
+
+impl<T> Default for WeakPtr<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: ?Sized> Clone for WeakPtr<T> {
+  fn clone(&self) -> Self {
+    let ptr = self.ptr;
+    let mut rc = self.rc;
+
+    if let Some(rc) = rc.as_opt_mut() {
+      rc.retain_weak();
+    }
+
+    Self { ptr, rc }
+  }
+}
+
+impl<T: ?Sized> Drop for WeakPtr<T> {
+  fn drop(&mut self) {
+    if self.rc.present() {
+      RefCount::release_weak(self.rc);
+    }
+  }
+}
@@ -0,0 +1,40 @@
+//! Process-wide counts of `SmartPtr` allocations, enabled by the `stats` feature
+//!
+//! Every constructor counts one allocation and the last strong reference going away releases it, clones do not
+//! touch the counters. A weak pointer may keep the memory itself around for longer, it is not counted as live.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) fn record_alloc() {
+  ALLOCATED.fetch_add(1, Ordering::Relaxed);
+  let live = LIVE.fetch_add(1, Ordering::Relaxed) + 1;
+  PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+pub(crate) fn record_release() {
+  LIVE.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Allocations made since the program started
+pub fn allocated() -> usize {
+  ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// Allocations whose value has not been dropped yet
+pub fn live() -> usize {
+  LIVE.load(Ordering::Relaxed)
+}
+
+/// The most allocations live at once since the program started or the last `reset_peak`
+pub fn peak() -> usize {
+  PEAK.load(Ordering::Relaxed)
+}
+
+/// Starts tracking the peak again from the current live count
+pub fn reset_peak() {
+  PEAK.store(live(), Ordering::Relaxed);
+}
@@ -0,0 +1,184 @@
+use super::MutPtr;
+use alloc::boxed::Box;
+use core::{
+  fmt::{Debug, Display, Error, Formatter},
+  ops::Deref,
+  sync::atomic::{fence, AtomicUsize, Ordering},
+};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+pub struct SyncSmartPtr<T> {
+  ptr: MutPtr<T>,
+  rc: MutPtr<AtomicUsize>,
+}
+
+unsafe impl<T: Send + Sync> Send for SyncSmartPtr<T> {}
+unsafe impl<T: Send + Sync> Sync for SyncSmartPtr<T> {}
+
+impl<T> SyncSmartPtr<T> {
+  pub fn new(item: T) -> Self {
+    let ptr = MutPtr::new(Box::leak(Box::new(item)));
+    let rc = MutPtr::new(Box::leak(Box::new(AtomicUsize::new(1))));
+
+    Self { ptr, rc }
+  }
+
+  pub fn valid(&self) -> bool {
+    self.ptr.present() && self.rc.present() && self.rc.load(Ordering::Acquire) > 0
+  }
+
+  #[cfg(all(test, feature = "std"))]
+  pub(crate) fn set_strong_count(&self, count: usize) {
+    self.rc.store(count, Ordering::Relaxed);
+  }
+
+  pub fn access(&self) -> &T {
+    unsafe { &*self.ptr.raw() }
+  }
+
+  /// Panics if another clone shares the value, since it could be reading it on another thread
+  #[track_caller]
+  pub fn access_mut(&mut self) -> &mut T {
+    self.get_mut().expect("mutably accessing a shared SyncSmartPtr")
+  }
+
+  /// Mutable access only while no other clone exists, like `Arc::get_mut`
+  pub fn get_mut(&mut self) -> Option<&mut T> {
+    if self.is_unique() {
+      Some(unsafe { &mut *self.ptr.raw() })
+    } else {
+      None
+    }
+  }
+
+  pub fn strong_count(&self) -> usize {
+    if self.valid() {
+      self.rc.load(Ordering::Acquire)
+    } else {
+      0
+    }
+  }
+
+  /// The acquire load pairs with the release decrement of every dropped clone, so their accesses happen before ours
+  pub fn is_unique(&self) -> bool {
+    self.strong_count() == 1
+  }
+}
+
+impl<T> Default for SyncSmartPtr<T>
+where
+  T: Default,
+{
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
+
+impl<T> Drop for SyncSmartPtr<T> {
+  fn drop(&mut self) {
+    if self.valid() && self.rc.fetch_sub(1, Ordering::Release) == 1 {
+      // synchronize with every other release decrement before tearing down the value
+      fence(Ordering::Acquire);
+      unsafe {
+        let _ = Box::from_raw(self.ptr.raw());
+        let _ = Box::from_raw(self.rc.raw());
+      }
+    }
+  }
+}
+
+/// Shared access only, clones on other threads may be reading at the same time, mutate through `get_mut` or a lock
+impl<T> Deref for SyncSmartPtr<T> {
+  type Target = T;
+  fn deref(&self) -> &Self::Target {
+    self.access()
+  }
+}
+
+impl<T> Clone for SyncSmartPtr<T> {
+  /// Panics if the strong count would pass `isize::MAX`
+  fn clone(&self) -> Self {
+    let ptr = self.ptr;
+    let rc = self.rc;
+
+    if rc.fetch_add(1, Ordering::Relaxed) >= isize::MAX as usize {
+      rc.fetch_sub(1, Ordering::Relaxed);
+      panic!("SyncSmartPtr strong count overflowed");
+    }
+
+    Self { ptr, rc }
+  }
+}
+
+impl<T: Debug> Debug for SyncSmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    self.access().fmt(f)
+  }
+}
+
+impl<T: Display> Display for SyncSmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    self.access().fmt(f)
+  }
+}
+
+impl<T: PartialEq> PartialEq<SyncSmartPtr<T>> for SyncSmartPtr<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.access().eq(other.access())
+  }
+}
+
+/// A shared slot whose `SyncSmartPtr` readers clone out while a writer replaces the whole value.
+///
+/// Loads and stores go through an internal lock held only for the count update, so a reader always ends up with
+/// either the old or the new value and keeps it alive for as long as it holds the clone.
+#[cfg(feature = "std")]
+pub struct AtomicSmartPtr<T>(Mutex<SyncSmartPtr<T>>);
+
+#[cfg(feature = "std")]
+impl<T> AtomicSmartPtr<T> {
+  pub fn new(ptr: SyncSmartPtr<T>) -> Self {
+    Self(Mutex::new(ptr))
+  }
+
+  /// A new strong reference to the current value
+  pub fn load(&self) -> SyncSmartPtr<T> {
+    self.lock().clone()
+  }
+
+  /// Replaces the value, the old one is released after the lock is given up
+  pub fn store(&self, ptr: SyncSmartPtr<T>) {
+    drop(self.swap(ptr));
+  }
+
+  pub fn swap(&self, ptr: SyncSmartPtr<T>) -> SyncSmartPtr<T> {
+    core::mem::replace(&mut *self.lock(), ptr)
+  }
+
+  pub fn into_inner(self) -> SyncSmartPtr<T> {
+    self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+  }
+
+  // nothing panics while the lock is held, but a poisoned slot still holds a consistent pointer
+  fn lock(&self) -> MutexGuard<'_, SyncSmartPtr<T>> {
+    self.0.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for AtomicSmartPtr<T>
+where
+  T: Default,
+{
+  fn default() -> Self {
+    Self::new(SyncSmartPtr::default())
+  }
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> Debug for AtomicSmartPtr<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+    self.load().fmt(f)
+  }
+}
@@ -0,0 +1,82 @@
+use super::MutPtr;
+use core::{
+  fmt::{self, Debug, Formatter},
+  mem,
+  ops::{Deref, DerefMut},
+};
+
+/// A `MutPtr` carrying a `BITS` wide tag in the low bits its alignment leaves unused
+///
+/// Dereferencing masks the tag off, so the pointee is reached as if the pointer were untagged
+pub struct TaggedPtr<T, const BITS: u32>(MutPtr<T>);
+
+impl<T, const BITS: u32> TaggedPtr<T, BITS> {
+  const MASK: usize = {
+    assert!(
+      1 << BITS <= mem::align_of::<T>(),
+      "the alignment of T leaves too few low bits for the tag"
+    );
+    (1 << BITS) - 1
+  };
+
+  /// Panics if `tag` does not fit in `BITS` bits
+  pub fn new(ptr: MutPtr<T>, tag: usize) -> Self {
+    debug_assert!(
+      ptr.0.addr() & Self::MASK == 0,
+      "pointer is not aligned to {} bytes",
+      mem::align_of::<T>()
+    );
+    let mut tagged = Self(ptr);
+    tagged.set_tag(tag);
+    tagged
+  }
+
+  pub fn tag(&self) -> usize {
+    self.0 .0.addr() & Self::MASK
+  }
+
+  /// Panics if `tag` does not fit in `BITS` bits
+  pub fn set_tag(&mut self, tag: usize) {
+    assert!(tag <= Self::MASK, "tag {} does not fit in {} bits", tag, BITS);
+    self.0 .0 = self.0 .0.map_addr(|addr| (addr & !Self::MASK) | tag);
+  }
+
+  pub fn untagged(&self) -> MutPtr<T> {
+    MutPtr(self.0 .0.map_addr(|addr| addr & !Self::MASK), self.0 .1)
+  }
+}
+
+impl<T, const BITS: u32> Clone for TaggedPtr<T, BITS> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T, const BITS: u32> Copy for TaggedPtr<T, BITS> {}
+
+impl<T, const BITS: u32> Deref for TaggedPtr<T, BITS> {
+  type Target = T;
+  #[cfg_attr(any(feature = "checked", debug_assertions), track_caller)]
+  fn deref(&self) -> &Self::Target {
+    let ptr = self.untagged();
+    debug_assert!(ptr.present(), "dereferenced a null TaggedPtr");
+    ptr.1.verify("TaggedPtr");
+    unsafe { &*ptr.0 }
+  }
+}
+
+impl<T, const BITS: u32> DerefMut for TaggedPtr<T, BITS> {
+  #[cfg_attr(any(feature = "checked", debug_assertions), track_caller)]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    let ptr = self.untagged();
+    debug_assert!(ptr.present(), "dereferenced a null TaggedPtr");
+    ptr.1.verify("TaggedPtr");
+    unsafe { &mut *ptr.0 }
+  }
+}
+
+impl<T, const BITS: u32> Debug for TaggedPtr<T, BITS> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "TaggedPtr({:p}, tag: {})", self.untagged().0, self.tag())
+  }
+}
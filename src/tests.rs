@@ -30,17 +30,17 @@ fn smart_pointer_drops() {
     let test_ptr = SmartPtr::new(test);
     assert!(!dropped);
 
-    assert_eq!(test_ptr.count(), 1);
+    assert_eq!(test_ptr.strong_count(), 1);
 
     {
       let test_ptr_cpy = test_ptr.clone();
       assert!(!dropped);
 
-      assert_eq!(test_ptr.count(), 2);
-      assert_eq!(test_ptr_cpy.count(), 2);
+      assert_eq!(test_ptr.strong_count(), 2);
+      assert_eq!(test_ptr_cpy.strong_count(), 2);
     }
 
-    assert_eq!(test_ptr.count(), 1);
+    assert_eq!(test_ptr.strong_count(), 1);
     assert!(!dropped);
   }
 
@@ -55,7 +55,7 @@ fn smart_pointer_keeps_alive() {
 
   impl TestStruct {
     fn new(mut ptr: SmartPtr<usize>) -> Self {
-      **ptr = 1;
+      *ptr = 1;
       Self { ptr }
     }
   }
@@ -69,17 +69,3096 @@ fn smart_pointer_keeps_alive() {
     }
 
     assert!(t.ptr.valid());
-    assert_eq!(**t.ptr, 1);
+    assert_eq!(*t.ptr, 1);
   }
   {
     let ptr = SmartPtr::new(0usize);
 
     {
-      let _t: TestStruct;
-      _t = TestStruct::new(ptr.clone());
+      let _t = TestStruct::new(ptr.clone());
     }
 
     assert!(ptr.valid());
-    assert_eq!(**ptr, 1);
+    assert_eq!(*ptr, 1);
   }
 }
+
+#[test]
+fn weak_pointer_upgrades_while_strong_alive() {
+  let strong = SmartPtr::new(5usize);
+  let weak = strong.downgrade();
+
+  {
+    let upgraded = weak.upgrade();
+    assert!(upgraded.is_some());
+    assert_eq!(strong.strong_count(), 2);
+    assert_eq!(*upgraded.unwrap(), 5);
+  }
+
+  assert_eq!(strong.strong_count(), 1);
+
+  drop(strong);
+
+  assert!(weak.upgrade().is_none());
+  assert!(WeakPtr::<usize>::new().upgrade().is_none());
+}
+
+#[test]
+fn weak_back_pointer_does_not_leak() {
+  struct Parent {
+    child: SmartPtr<Child>,
+    dropped: MutPtr<bool>,
+  }
+
+  impl Drop for Parent {
+    fn drop(&mut self) {
+      *self.dropped = true;
+    }
+  }
+
+  struct Child {
+    parent: WeakPtr<Parent>,
+    dropped: MutPtr<bool>,
+  }
+
+  impl Drop for Child {
+    fn drop(&mut self) {
+      *self.dropped = true;
+    }
+  }
+
+  let mut parent_dropped = false;
+  let mut child_dropped = false;
+
+  {
+    let child = SmartPtr::new(Child {
+      parent: WeakPtr::default(),
+      dropped: MutPtr::new(&mut child_dropped),
+    });
+
+    let parent = SmartPtr::new(Parent {
+      child: child.clone(),
+      dropped: MutPtr::new(&mut parent_dropped),
+    });
+
+    let mut child = child;
+    child.access_mut().parent = parent.downgrade();
+
+    assert!(parent.child.parent.upgrade().is_some());
+    assert_eq!(parent.strong_count(), 1);
+    assert_eq!(child.strong_count(), 2);
+  }
+
+  assert!(parent_dropped);
+  assert!(child_dropped);
+}
+
+#[test]
+fn sync_smart_pointer_drops_once_across_threads() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+  struct Test(usize);
+
+  impl Drop for Test {
+    fn drop(&mut self) {
+      DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+  }
+
+  let ptr = SyncSmartPtr::new(Test(7));
+
+  let handles = (0..8)
+    .map(|_| {
+      let ptr = ptr.clone();
+      std::thread::spawn(move || {
+        assert!(ptr.valid());
+        assert_eq!(ptr.access().0, 7);
+      })
+    })
+    .collect::<Vec<_>>();
+
+  for handle in handles {
+    handle.join().unwrap();
+  }
+
+  assert_eq!(ptr.strong_count(), 1);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+  drop(ptr);
+
+  assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn sync_smart_pointer_mutates_only_when_unique() {
+  let mut ptr = SyncSmartPtr::new(1);
+  *ptr.access_mut() += 1;
+
+  let clone = ptr.clone();
+  assert!(!ptr.is_unique());
+  assert!(ptr.get_mut().is_none());
+  assert_eq!(*clone, 2);
+
+  std::thread::spawn(move || drop(clone)).join().unwrap();
+  assert!(ptr.is_unique());
+  *ptr.get_mut().unwrap() += 1;
+  assert_eq!(*ptr, 3);
+}
+
+#[test]
+#[should_panic(expected = "mutably accessing a shared SyncSmartPtr")]
+fn sync_smart_pointer_shared_access_mut_panics() {
+  let mut ptr = SyncSmartPtr::new(1);
+  let _clone = ptr.clone();
+  *ptr.access_mut() += 1;
+}
+
+#[test]
+fn unsized_pointees() {
+  trait Named {
+    fn name(&self) -> &'static str;
+  }
+
+  struct Thing;
+
+  impl Named for Thing {
+    fn name(&self) -> &'static str {
+      "thing"
+    }
+  }
+
+  let thing = Thing;
+  let named: &dyn Named = &thing;
+  let mut dyn_ptr = ConstPtr::new(named);
+  assert!(dyn_ptr.present());
+  assert_eq!(dyn_ptr.name(), "thing");
+  dyn_ptr.clear();
+  assert!(dyn_ptr.null());
+
+  let mut buffer = [1u8, 2, 3];
+  let mut slice_ptr = MutPtr::new(&mut buffer[..]);
+  assert_eq!(slice_ptr.len(), 3);
+  slice_ptr[1] = 5;
+  let slice_cpy = slice_ptr;
+  assert_eq!(&*slice_cpy, &[1, 5, 3]);
+  slice_ptr.clear();
+  assert!(slice_ptr.null());
+  assert!(slice_cpy.present());
+  assert_eq!(buffer, [1, 5, 3]);
+
+  let text = String::from("hello");
+  let str_ptr = ConstPtr::new(text.as_str());
+  assert_eq!(str_ptr.as_ref(), "hello");
+  assert_eq!(str_ptr.len(), 5);
+}
+
+#[test]
+fn smart_pointer_to_trait_object() {
+  trait Renderer {
+    fn render(&self) -> usize;
+  }
+
+  struct GlRenderer {
+    frames: usize,
+    dropped: MutPtr<bool>,
+  }
+
+  impl Renderer for GlRenderer {
+    fn render(&self) -> usize {
+      self.frames
+    }
+  }
+
+  impl Drop for GlRenderer {
+    fn drop(&mut self) {
+      *self.dropped = true;
+    }
+  }
+
+  let mut dropped = false;
+
+  {
+    let renderer = SmartPtr::<dyn Renderer>::from_box(Box::new(GlRenderer {
+      frames: 3,
+      dropped: MutPtr::new(&mut dropped),
+    }));
+    assert!(renderer.valid());
+    assert_eq!(renderer.access().render(), 3);
+
+    {
+      let renderer_cpy = renderer.clone();
+      assert_eq!(renderer_cpy.render(), 3);
+      assert_eq!(renderer.strong_count(), 2);
+    }
+
+    assert!(!dropped);
+  }
+
+  assert!(dropped);
+}
+
+#[test]
+fn typed_casts() {
+  #[repr(C)]
+  #[derive(Debug, PartialEq)]
+  struct Header {
+    kind: u16,
+    len: u16,
+  }
+
+  #[repr(C, align(4))]
+  struct Buffer([u8; 8]);
+
+  let mut buffer = Buffer([0; 8]);
+  buffer.0[..4].copy_from_slice(&[1, 0, 2, 0]);
+
+  let bytes = ConstPtr::new(&buffer.0);
+  let header = bytes.cast_aligned::<Header>();
+  assert_eq!(header.raw() as usize, bytes.raw() as usize);
+  assert_eq!(u16::from_le(header.kind), 1);
+  assert_eq!(u16::from_le(header.len), 2);
+
+  let mut header = MutPtr::from(buffer.0.as_mut_ptr().wrapping_add(4)).cast_aligned::<Header>();
+  header.kind = u16::to_le(3);
+  assert_eq!(buffer.0[4], 3);
+
+  let erased = ConstPtr::new(&buffer).erase();
+  assert_eq!(erased.cast::<Buffer>().as_ref().0[0], 1);
+
+  let null = ConstPtr::<u8>::default().cast::<Header>();
+  assert!(null.null());
+  assert!(MutPtr::<u8>::default().erase().null());
+}
+
+#[test]
+#[should_panic(expected = "not aligned")]
+fn misaligned_cast_panics() {
+  #[repr(C, align(4))]
+  struct Buffer([u8; 8]);
+
+  let buffer = Buffer([0; 8]);
+  ConstPtr::new(&buffer.0[1]).cast_aligned::<u32>();
+}
+
+#[test]
+fn optional_access() {
+  let mut value = 3usize;
+
+  let const_ptr = ConstPtr::new(&value);
+  assert_eq!(const_ptr.as_opt(), Some(&3));
+  assert_eq!(ConstPtr::<usize>::default().as_opt(), None);
+
+  let mut mut_ptr = MutPtr::new(&mut value);
+  assert_eq!(mut_ptr.as_opt(), Some(&3));
+  if let Some(value) = mut_ptr.as_opt_mut() {
+    *value = 4;
+  }
+  assert_eq!(value, 4);
+
+  let mut null = MutPtr::<usize>::default();
+  assert_eq!(null.as_opt(), None);
+  assert_eq!(null.as_opt_mut(), None);
+
+  let mut cleared = MutPtr::new(&mut value);
+  cleared.clear();
+  assert_eq!(cleared.as_opt_mut(), None);
+}
+
+#[test]
+fn fallible_deref() {
+  fn read(ptr: ConstPtr<usize>) -> Result<usize, PtrError> {
+    Ok(*ptr.try_deref()?)
+  }
+
+  let mut value = 1usize;
+  assert_eq!(read(ConstPtr::new(&value)), Ok(1));
+  assert_eq!(read(ConstPtr::default()), Err(PtrError::Null));
+
+  let mut ptr = MutPtr::new(&mut value);
+  *ptr.try_deref_mut().unwrap() = 2;
+  assert_eq!(ptr.try_deref(), Ok(&2));
+
+  ptr.clear();
+  assert_eq!(ptr.try_deref_mut(), Err(PtrError::Null));
+  assert_eq!(PtrError::Null.to_string(), "pointer is null");
+
+  let mut smart = SmartPtr::new(3usize);
+  assert_eq!(smart.try_access(), Ok(&3));
+
+  *smart += 1;
+  assert_eq!(*smart.try_access().unwrap(), 4);
+
+  let empty = SmartPtr::<usize>::empty();
+  assert!(!empty.valid());
+  assert_eq!(empty.try_access(), Err(PtrError::Null));
+}
+
+const _: () = assert!(std::mem::size_of::<Option<NonNullConstPtr<u8>>>() == std::mem::size_of::<*const u8>());
+const _: () = assert!(std::mem::size_of::<Option<NonNullMutPtr<u8>>>() == std::mem::size_of::<*mut u8>());
+
+#[test]
+fn non_null_pointers() {
+  let mut value = 1usize;
+
+  let mut mut_ptr = NonNullMutPtr::new(&mut value);
+  *mut_ptr = 2;
+  *mut_ptr.as_mut() += 1;
+  assert_eq!(*mut_ptr, 3);
+
+  let const_ptr = NonNullConstPtr::from(mut_ptr);
+  assert_eq!(*const_ptr.as_ref(), 3);
+  assert_eq!(const_ptr.raw(), mut_ptr.raw().cast_const());
+
+  let loose = ConstPtr::from(const_ptr);
+  assert_eq!(loose.raw(), const_ptr.raw());
+  assert!(NonNullConstPtr::try_from(loose).is_ok());
+  assert!(NonNullConstPtr::try_from(ConstPtr::<usize>::default()).is_err());
+
+  let loose = MutPtr::from(mut_ptr);
+  assert_eq!(loose.raw(), mut_ptr.raw());
+  assert_eq!(
+    NonNullMutPtr::try_from(MutPtr::<usize>::default()).err(),
+    Some(PtrError::Null)
+  );
+  assert_eq!(
+    NonNullMutPtr::try_from(std::ptr::null_mut::<usize>()).err(),
+    Some(PtrError::Null)
+  );
+  assert_eq!(*NonNullMutPtr::try_from(&mut value as *mut usize).unwrap(), 3);
+}
+
+#[test]
+fn pointers_hash_and_compare_by_address() {
+  use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+
+  fn hash_of<H: std::hash::Hash>(value: &H) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    std::hash::Hasher::finish(&hasher)
+  }
+
+  let a = 1usize;
+  let b = 1usize;
+
+  assert!(ConstPtr::new(&a) == ConstPtr::new(&a));
+  assert_eq!(hash_of(&ConstPtr::new(&a)), hash_of(&ConstPtr::new(&a)));
+  assert!(ConstPtr::new(&a) != ConstPtr::new(&b));
+  assert!(ConstPtr::<usize>::default() == ConstPtr::default());
+  assert!(MutPtr::<usize>::default() == MutPtr::default());
+
+  let mut visited = HashSet::new();
+  assert!(visited.insert(ConstPtr::new(&a)));
+  assert!(!visited.insert(ConstPtr::new(&a)));
+  assert!(visited.insert(ConstPtr::new(&b)));
+
+  let mut c = 2usize;
+  let mut names = HashMap::new();
+  names.insert(MutPtr::new(&mut c), "c");
+  assert_eq!(names.get(&MutPtr::new(&mut c)), Some(&"c"));
+}
+
+#[test]
+fn pointers_order_by_address() {
+  let mut entities = [10usize, 5, 7, 1];
+
+  let mut ptrs = [
+    ConstPtr::new(&entities[2]),
+    ConstPtr::default(),
+    ConstPtr::new(&entities[0]),
+    ConstPtr::new(&entities[3]),
+    ConstPtr::new(&entities[1]),
+  ];
+  ptrs.sort();
+
+  assert!(ptrs[0].null());
+  for (i, ptr) in ptrs[1..].iter().enumerate() {
+    assert!(*ptr == ConstPtr::new(&entities[i]));
+  }
+  assert_eq!(ptrs.binary_search(&ConstPtr::new(&entities[2])), Ok(3));
+
+  let base = MutPtr::new(&mut entities[0]).raw();
+  let mut ptrs = (0..4).rev().map(|i| MutPtr::from(base.wrapping_add(i))).collect::<Vec<_>>();
+  ptrs.push(MutPtr::default());
+  ptrs.sort();
+
+  assert!(ptrs[0].null());
+  assert!(ptrs[1..].iter().enumerate().all(|(i, ptr)| ptr.raw() == base.wrapping_add(i)));
+  assert!(MutPtr::<usize>::default() < MutPtr::from(base));
+}
+
+#[test]
+fn pointer_formatting() {
+  let mut value = 4usize;
+  let expected = format!("{:p}", &value);
+
+  let const_ptr = ConstPtr::new(&value);
+  assert_eq!(format!("{:p}", const_ptr), expected);
+  assert!(usize::from_str_radix(expected.trim_start_matches("0x"), 16).is_ok());
+
+  let mut_ptr = MutPtr::new(&mut value);
+  assert_eq!(format!("{:p}", mut_ptr), expected);
+
+  assert_eq!(format!("{:p}", ConstPtr::<usize>::default()), "0x0");
+  assert_eq!(format!("{:p}", MutPtr::<usize>::default()), "0x0");
+
+  let smart = SmartPtr::new(1usize);
+  assert_eq!(format!("{:p}", smart), format!("{:p}", smart.access()));
+}
+
+#[test]
+fn debug_formatting_is_null_safe() {
+  #[derive(Debug, Default)]
+  struct Node {
+    next: MutPtr<usize>,
+    value: ConstPtr<usize>,
+  }
+
+  let node = Node::default();
+  assert!(node.next.null() && node.value.null());
+  assert_eq!(format!("{:?}", node), "Node { next: MutPtr(null), value: ConstPtr(null) }");
+
+  let value = 5usize;
+  let ptr = ConstPtr::new(&value);
+  assert_eq!(format!("{:?}", ptr), format!("ConstPtr({:p} -> 5)", &value));
+
+  let smart = SmartPtr::new(6usize);
+  assert_eq!(format!("{:?}", smart), "6");
+}
+
+#[test]
+fn display_forwarding_is_null_safe() {
+  let value = 1.23456f64;
+  let ptr = ConstPtr::new(&value);
+  assert_eq!(ptr.to_string(), "1.23456");
+  assert_eq!(format!("{:>8.2}", ptr), "    1.23");
+
+  let mut name = String::from("name");
+  let ptr = MutPtr::new(&mut name);
+  assert_eq!(format!("{:<6}|", ptr), "name  |");
+
+  assert_eq!(ConstPtr::<f64>::default().to_string(), "<null>");
+  assert_eq!(format!("{:>8}", MutPtr::<String>::default()), "  <null>");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_pointers() {
+  #[derive(serde::Serialize)]
+  struct Config {
+    name: &'static str,
+  }
+
+  #[derive(serde::Serialize)]
+  struct Snapshot {
+    config: ConstPtr<Config>,
+    missing: ConstPtr<Config>,
+    counter: MutPtr<usize>,
+    empty: MutPtr<usize>,
+    shared: SmartPtr<usize>,
+  }
+
+  let config = Config { name: "main" };
+  let mut counter = 3usize;
+
+  let snapshot = Snapshot {
+    config: ConstPtr::new(&config),
+    missing: ConstPtr::default(),
+    counter: MutPtr::new(&mut counter),
+    empty: MutPtr::default(),
+    shared: SmartPtr::new(4),
+  };
+
+  assert_eq!(
+    serde_json::to_string(&snapshot).unwrap(),
+    r#"{"config":{"name":"main"},"missing":null,"counter":3,"empty":null,"shared":4}"#
+  );
+
+  let shared: SmartPtr<usize> = serde_json::from_str("5").unwrap();
+  assert_eq!(*shared.access(), 5);
+}
+
+#[cfg(feature = "leaky-deserialize")]
+#[test]
+fn leaky_deserialize_round_trip() {
+  #[derive(serde::Serialize, serde::Deserialize)]
+  struct Graph {
+    root: MutPtr<usize>,
+    parent: ConstPtr<String>,
+    missing: MutPtr<usize>,
+  }
+
+  let json = r#"{"root":1,"parent":"top","missing":null}"#;
+  let graph: Graph = serde_json::from_str(json).unwrap();
+
+  assert_eq!(*graph.root, 1);
+  assert_eq!(graph.parent.as_str(), "top");
+  assert!(graph.missing.null());
+  assert_eq!(serde_json::to_string(&graph).unwrap(), json);
+
+  unsafe {
+    drop(Box::from_raw(graph.root.raw()));
+    drop(Box::from_raw(graph.parent.raw().cast_mut()));
+  }
+}
+
+#[test]
+fn strong_count_tracks_clones() {
+  let ptr = SmartPtr::new(1usize);
+  assert_eq!(ptr.strong_count(), 1);
+
+  let first = ptr.clone();
+  let second = first.clone();
+  assert_eq!(ptr.strong_count(), 3);
+  assert_eq!(second.strong_count(), 3);
+
+  drop(first);
+  assert_eq!(second.strong_count(), 2);
+
+  drop(second);
+  assert_eq!(ptr.strong_count(), 1);
+
+  assert_eq!(SmartPtr::<usize>::empty().strong_count(), 0);
+}
+
+#[test]
+fn smart_pointer_identity() {
+  let a = SmartPtr::new(String::from("mesh"));
+  let b = SmartPtr::new(String::from("mesh"));
+  let a_cpy = a.clone();
+
+  assert!(a == b);
+  assert!(!a.ptr_eq(&b));
+  assert!(a.ptr_eq(&a_cpy));
+  assert!(a.ptr_eq(&a));
+}
+
+#[test]
+fn smart_pointer_unwraps_unique_value() {
+  use std::cell::Cell;
+
+  struct Counted<'a>(&'a Cell<usize>);
+
+  impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+
+  let ptr = SmartPtr::new(Counted(&drops));
+  let weak = ptr.downgrade();
+  let value = ptr.try_unwrap().ok().unwrap();
+  assert_eq!(drops.get(), 0);
+  assert!(weak.upgrade().is_none());
+  drop(value);
+  assert_eq!(drops.get(), 1);
+
+  let ptr = SmartPtr::new(Counted(&drops));
+  let ptr_cpy = ptr.clone();
+  let ptr = ptr.try_unwrap().err().unwrap();
+  assert_eq!(ptr.strong_count(), 2);
+  assert!(ptr.into_inner().is_none());
+  assert_eq!(drops.get(), 1);
+
+  let value = ptr_cpy.into_inner();
+  assert!(value.is_some());
+  assert_eq!(drops.get(), 1);
+  drop(value);
+  assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn smart_pointer_get_mut_requires_unique() {
+  let mut ptr = SmartPtr::new(1usize);
+  *ptr.get_mut().unwrap() = 2;
+
+  let ptr_cpy = ptr.clone();
+  assert!(ptr.get_mut().is_none());
+  drop(ptr_cpy);
+
+  let weak = ptr.downgrade();
+  assert!(ptr.get_mut().is_none());
+  drop(weak);
+
+  *ptr.get_mut().unwrap() += 1;
+  assert_eq!(*ptr.access(), 3);
+}
+
+#[test]
+fn smart_pointer_make_mut_clones_on_write() {
+  use std::cell::Cell;
+
+  struct Config<'a> {
+    value: usize,
+    drops: &'a Cell<usize>,
+  }
+
+  impl Clone for Config<'_> {
+    fn clone(&self) -> Self {
+      Self {
+        value: self.value,
+        drops: self.drops,
+      }
+    }
+  }
+
+  impl Drop for Config<'_> {
+    fn drop(&mut self) {
+      self.drops.set(self.drops.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+
+  let mut ptr = SmartPtr::new(Config { value: 1, drops: &drops });
+  let original = ptr.clone();
+
+  ptr.make_mut().value = 2;
+  assert!(!ptr.ptr_eq(&original));
+  assert_eq!(ptr.value, 2);
+  assert_eq!(original.value, 1);
+  assert_eq!(original.strong_count(), 1);
+  assert_eq!(drops.get(), 0);
+
+  let unique = ptr.as_mut_ptr().raw();
+  ptr.make_mut().value = 3;
+  assert_eq!(ptr.as_mut_ptr().raw(), unique);
+  assert_eq!(drops.get(), 0);
+
+  drop(original);
+  assert_eq!(drops.get(), 1);
+  drop(ptr);
+  assert_eq!(drops.get(), 2);
+}
+
+#[test]
+#[should_panic(expected = "make_mut on a SmartPtr without a value")]
+fn smart_pointer_make_mut_on_empty_panics() {
+  let mut empty = SmartPtr::<u32>::empty();
+  *empty.make_mut() += 1;
+}
+
+#[test]
+fn smart_pointer_adopts_box_in_place() {
+  let level = Box::new([7u8; 4096]);
+  let addr = level.as_ref() as *const [u8; 4096];
+
+  let ptr = SmartPtr::from_box(level);
+  assert_eq!(ptr.as_const_ptr().raw(), addr);
+  assert_eq!(ptr[4095], 7);
+
+  let slice: Box<[u8]> = vec![1, 2, 3].into_boxed_slice();
+  let addr = slice.as_ptr();
+  let ptr: SmartPtr<[u8]> = slice.into();
+  assert_eq!(ptr.as_const_ptr().cast::<u8>().raw(), addr);
+  assert_eq!(ptr.access(), &[1, 2, 3]);
+}
+
+#[test]
+fn smart_pointer_raw_round_trip() {
+  use std::{cell::Cell, ffi::c_void};
+
+  struct Counted<'a>(&'a Cell<usize>);
+
+  impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+
+  extern "C" fn callback(user_data: *mut c_void) -> *mut c_void {
+    user_data
+  }
+
+  let drops = Cell::new(0);
+
+  let ptr = SmartPtr::new(Counted(&drops));
+  let ptr_cpy = ptr.clone();
+
+  let user_data = callback(ptr.into_raw().cast::<c_void>());
+  assert_eq!(ptr_cpy.strong_count(), 2);
+  drop(ptr_cpy);
+  assert_eq!(drops.get(), 0);
+
+  let ptr = unsafe { SmartPtr::<Counted>::from_raw(user_data.cast()) };
+  assert_eq!(ptr.strong_count(), 1);
+  assert_eq!(drops.get(), 0);
+
+  drop(ptr);
+  assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn pointer_arithmetic() {
+  let values = [3usize, 1, 4, 1, 5];
+
+  let start = ConstPtr::from(values.as_ptr());
+  for (i, value) in values.iter().enumerate() {
+    assert_eq!(unsafe { *start.add(i) }, *value);
+  }
+
+  let last = unsafe { start.offset(4) };
+  assert_eq!(*last, 5);
+  assert_eq!(unsafe { *last.sub(2) }, 4);
+  assert!(last.wrapping_sub(4) == start);
+  assert!(start.wrapping_offset(2) == unsafe { start.add(2) });
+
+  let mut values = values;
+  let start = MutPtr::from(values.as_mut_ptr());
+  let mut third = start.wrapping_add(2);
+  *third = 9;
+  assert_eq!(values[2], 9);
+
+  assert!(ConstPtr::<usize>::default().wrapping_add(3).null());
+  assert!(MutPtr::<usize>::default().wrapping_offset(-3).null());
+}
+
+#[test]
+fn mut_pointer_value_operations() {
+  let mut first = String::from("first");
+  let mut second = String::from("second");
+
+  let mut first_ptr = MutPtr::new(&mut first);
+  let mut second_ptr = MutPtr::new(&mut second);
+
+  let old = first_ptr.replace(String::from("replaced"));
+  assert_eq!(old, "first");
+
+  first_ptr.swap(&mut second_ptr);
+  assert_eq!(first, "second");
+  assert_eq!(second, "replaced");
+
+  let mut slot = std::mem::MaybeUninit::<usize>::uninit();
+  let mut slot_ptr = MutPtr::new(&mut slot).cast::<usize>();
+  slot_ptr.write(4);
+  assert_eq!(slot_ptr.read(), 4);
+  assert_eq!(unsafe { slot.assume_init() }, 4);
+}
+
+#[test]
+fn swap_pointees_between_buffers() {
+  use std::cell::Cell;
+
+  struct Frame<'a> {
+    id: usize,
+    drops: &'a Cell<usize>,
+  }
+
+  impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+      self.drops.set(self.drops.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+
+  {
+    let mut front = Frame { id: 0, drops: &drops };
+    let mut back = Frame { id: 1, drops: &drops };
+
+    let front_ptr = MutPtr::new(&mut front);
+    let back_ptr = MutPtr::new(&mut back);
+
+    swap_pointees(front_ptr, back_ptr);
+    swap_pointees(front_ptr, back_ptr);
+    swap_pointees(front_ptr, back_ptr);
+    swap_pointees(front_ptr, front_ptr);
+
+    assert_eq!(front.id, 1);
+    assert_eq!(back.id, 0);
+    assert_eq!(drops.get(), 0);
+  }
+
+  assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn const_slice_pointers() {
+  let values = vec![2usize, 4, 6];
+  let ptr = ConstPtr::from(values.as_slice());
+
+  assert_eq!(ptr.len(), 3);
+  assert!(!ptr.is_empty());
+  assert_eq!(ptr[1], 4);
+  assert_eq!(*ptr.get(2).unwrap(), 6);
+  assert!(ptr.get(3).is_none());
+
+  let raw: *const [usize] = ptr.raw();
+  let round_trip = ConstPtr::from_slice(unsafe { &*raw });
+  assert_eq!(round_trip.len(), values.len());
+  assert!(ConstPtr::from_slice(&values[..0]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn const_slice_pointer_index_out_of_bounds() {
+  let values = [1usize, 2];
+  let ptr = ConstPtr::from_slice(&values);
+  let _ = ptr[2];
+}
+
+#[test]
+fn mut_slice_pointers() {
+  let mut vertices = vec![0.0f32; 4];
+  let mut ptr = MutPtr::from(vertices.as_mut_slice());
+
+  assert_eq!(ptr.len(), 4);
+  ptr[0] = 1.0;
+  *ptr.get_mut(1).unwrap() = 2.0;
+  assert!(ptr.get_mut(4).is_none());
+  assert_eq!(ptr[1], 2.0);
+  assert_eq!(vertices, [1.0, 2.0, 0.0, 0.0]);
+
+  let mut ptr = MutPtr::from_slice(&mut vertices[2..]);
+  ptr.fill(5.0);
+  assert_eq!(vertices, [1.0, 2.0, 5.0, 5.0]);
+
+  let mut names = vec![String::new(); 3];
+  let mut next = 0;
+  MutPtr::from_slice(names.as_mut_slice()).fill_with(|| {
+    next += 1;
+    next.to_string()
+  });
+  assert_eq!(names, ["1", "2", "3"]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn mut_slice_pointer_index_out_of_bounds() {
+  let mut values = [1usize, 2];
+  let mut ptr = MutPtr::from_slice(&mut values);
+  ptr[2] = 3;
+}
+
+#[test]
+fn str_pointers() {
+  let text = String::from("hello world");
+
+  let ptr = ConstPtr::from(&text);
+  assert_eq!(ptr.len(), 11);
+  assert!(ptr.starts_with("hello"));
+  assert_eq!(ptr.to_string(), "hello world");
+  assert_eq!(
+    format!("{:?}", ptr),
+    format!("ConstPtr({:p} -> \"hello world\")", text.as_ptr())
+  );
+  assert_eq!(ptr.as_bytes_ptr().len(), 11);
+  assert_eq!(ptr.as_bytes_ptr()[0], b'h');
+
+  let literal = ConstPtr::from("literal");
+  assert_eq!(&*literal, "literal");
+  assert!(ConstPtr::from("").is_empty());
+
+  let mut text = text;
+  let mut ptr = MutPtr::from(text.as_mut_str());
+  ptr.make_ascii_uppercase();
+  assert_eq!(ptr.len(), 11);
+  assert_eq!(text, "HELLO WORLD");
+}
+
+#[test]
+fn as_ptr_for_every_type() {
+  struct Local {
+    value: usize,
+  }
+
+  let number = 5u32;
+  assert_eq!(*number.const_ptr(), 5);
+
+  let mut values = vec![1, 2, 3];
+  values.mut_ptr().push(4);
+  assert_eq!(values.len(), 4);
+  assert_eq!(values.as_ptr(), values.const_ptr().as_ptr());
+
+  let mut local = Local { value: 1 };
+  local.mut_ptr().value = 2;
+  assert_eq!(local.const_ptr().value, 2);
+
+  let text = "unsized";
+  assert_eq!(text.const_ptr().len(), 7);
+}
+
+#[test]
+fn smart_pointer_pinning() {
+  use std::{
+    future::Future,
+    marker::PhantomPinned,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+  };
+
+  struct Intrusive {
+    value: usize,
+    _pinned: PhantomPinned,
+  }
+
+  fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+      RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+  }
+
+  let pinned = SmartPtr::pin(Intrusive {
+    value: 3,
+    _pinned: PhantomPinned,
+  });
+  let pinned_cpy = pinned.clone();
+  assert!(std::ptr::eq(pinned.as_ref().get_ref(), pinned_cpy.as_ref().get_ref()));
+  assert_eq!(pinned.value, 3);
+
+  let node = SmartPtr::new(Intrusive {
+    value: 4,
+    _pinned: PhantomPinned,
+  });
+  let pin_ref: Pin<&Intrusive> = unsafe { node.as_pin_ref() };
+  assert_eq!(pin_ref.get_ref() as *const Intrusive, node.as_const_ptr().raw());
+
+  let mut future = SmartPtr::new(async { 5usize });
+  let waker = noop_waker();
+  let mut cx = Context::from_waker(&waker);
+  let poll = unsafe { future.as_pin_mut() }.poll(&mut cx);
+  assert_eq!(poll, Poll::Ready(5));
+}
+
+#[test]
+fn send_and_sync_pointers_cross_threads() {
+  struct World {
+    ticks: usize,
+  }
+
+  let mut world = World { ticks: 0 };
+
+  let mut send = unsafe { SendPtr::new(MutPtr::new(&mut world)) };
+  std::thread::spawn(move || {
+    send.ticks += 1;
+  })
+  .join()
+  .unwrap();
+
+  assert_eq!(world.ticks, 1);
+
+  let sync = unsafe { SyncPtr::new(ConstPtr::new(&world)) };
+  let handles = (0..4).map(|_| std::thread::spawn(move || sync.ticks)).collect::<Vec<_>>();
+
+  for handle in handles {
+    assert_eq!(handle.join().unwrap(), 1);
+  }
+
+  assert_eq!(sync.into_inner().ticks, 1);
+}
+
+#[cfg(feature = "shared-const")]
+#[test]
+fn const_pointer_shared_across_scoped_threads() {
+  struct Config {
+    workers: usize,
+  }
+
+  let config = Config { workers: 4 };
+  let ptr = ConstPtr::new(&config);
+
+  let total = std::thread::scope(|scope| {
+    let handles = (0..config.workers).map(|_| scope.spawn(|| ptr.workers)).collect::<Vec<_>>();
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum::<usize>()
+  });
+
+  assert_eq!(total, 16);
+}
+
+#[cfg(all(feature = "checked", debug_assertions))]
+#[test]
+fn checked_deref_after_invalidate_panics() {
+  let mut value = 5;
+  let mut ptr = MutPtr::new(&mut value);
+  let copy = ptr;
+
+  assert_eq!(*copy, 5);
+  ptr.invalidate();
+  assert!(ptr.null());
+
+  let fresh = MutPtr::new(&mut value);
+  assert_eq!(*fresh, 5);
+
+  let err = std::panic::catch_unwind(|| *copy).unwrap_err();
+  let msg = err.downcast_ref::<String>().unwrap();
+  assert!(msg.starts_with("dereferenced an invalidated MutPtr"));
+  assert!(msg.contains(file!()));
+
+  let view = ConstPtr::from(copy);
+  assert!(std::panic::catch_unwind(|| view.as_opt().copied()).is_err());
+}
+
+#[cfg(all(feature = "checked", debug_assertions))]
+#[test]
+fn checked_table_forgets_retired_addresses() {
+  let before = crate::checked::tracked_addresses();
+  for i in 0..1000 {
+    let mut value = Box::new(i);
+    let mut ptr = MutPtr::new(&mut *value);
+    let copy = ConstPtr::new(&*value);
+    ptr.clear();
+    drop(value);
+    assert_eq!(copy.checked_ref(), Err(PtrError::Null));
+  }
+  assert_eq!(crate::checked::tracked_addresses(), before);
+}
+
+#[cfg(all(feature = "checked", debug_assertions))]
+#[test]
+#[should_panic(expected = "dereferenced an invalidated ConstPtr")]
+fn checked_deref_after_clear_panics() {
+  let value = 5;
+  let mut ptr = ConstPtr::new(&value);
+  let copy = ptr;
+  ptr.clear();
+  let _ = *copy;
+}
+
+#[cfg(all(feature = "checked", debug_assertions))]
+#[test]
+#[should_panic(expected = "dereferenced an invalidated MutPtr")]
+fn checked_borrow_mut_after_invalidate_panics() {
+  let mut value = 5;
+  let mut ptr = MutPtr::new(&mut value);
+  let mut copy = ptr;
+  ptr.invalidate();
+  *copy.borrow_mut() += 1;
+}
+
+#[test]
+fn tagged_pointer_round_trips_tags() {
+  #[repr(align(4))]
+  struct Object {
+    value: u32,
+  }
+
+  let mut object = Object { value: 7 };
+  let ptr = MutPtr::new(&mut object);
+
+  for tag in 0..4 {
+    let mut tagged = TaggedPtr::<Object, 2>::new(ptr, tag);
+    assert_eq!(tagged.tag(), tag);
+    assert!(tagged.untagged() == ptr);
+    assert_eq!(tagged.value, 7);
+
+    tagged.value += 1;
+    tagged.set_tag(3 - tag);
+    assert_eq!(tagged.tag(), 3 - tag);
+    assert_eq!(tagged.value, 8);
+    tagged.value -= 1;
+  }
+
+  assert_eq!(object.value, 7);
+}
+
+#[test]
+fn smart_pointer_allocates_through_custom_allocator() {
+  use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+  };
+
+  struct Counting {
+    allocs: AtomicUsize,
+    deallocs: AtomicUsize,
+  }
+
+  unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      self.allocs.fetch_add(1, Ordering::Relaxed);
+      System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      self.deallocs.fetch_add(1, Ordering::Relaxed);
+      System.dealloc(ptr, layout)
+    }
+  }
+
+  static COUNTING: Counting = Counting {
+    allocs: AtomicUsize::new(0),
+    deallocs: AtomicUsize::new(0),
+  };
+
+  let dropped = Cell::new(false);
+
+  struct Frame<'a> {
+    dropped: &'a Cell<bool>,
+  }
+
+  impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+      self.dropped.set(true);
+    }
+  }
+
+  let ptr = SmartPtr::new_in(Frame { dropped: &dropped }, &COUNTING);
+  let clone = ptr.clone();
+  let weak = ptr.downgrade();
+  assert_eq!(COUNTING.allocs.load(Ordering::Relaxed), 2);
+
+  drop(ptr);
+  drop(clone);
+  assert!(dropped.get());
+  assert_eq!(COUNTING.deallocs.load(Ordering::Relaxed), 1);
+
+  drop(weak);
+  assert_eq!(COUNTING.deallocs.load(Ordering::Relaxed), 2);
+
+  let value = SmartPtr::new_in(String::from("frame"), &COUNTING).try_unwrap().unwrap();
+  assert_eq!(value, "frame");
+  assert_eq!(COUNTING.allocs.load(Ordering::Relaxed), 4);
+  assert_eq!(COUNTING.deallocs.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn smart_pointer_clone_refuses_to_overflow() {
+  let mut ptr = SmartPtr::new(5);
+  ptr.set_strong_count(usize::MAX);
+
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| ptr.clone()));
+  assert!(result.is_err());
+  assert_eq!(ptr.strong_count(), usize::MAX);
+
+  ptr.set_strong_count(1);
+  let clone = ptr.clone();
+  assert_eq!(clone.strong_count(), 2);
+}
+
+#[test]
+fn sync_smart_pointer_clone_refuses_to_overflow() {
+  let ptr = SyncSmartPtr::new(5);
+  ptr.set_strong_count(isize::MAX as usize);
+
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| ptr.clone()));
+  assert!(result.is_err());
+  assert_eq!(ptr.strong_count(), isize::MAX as usize);
+
+  ptr.set_strong_count(1);
+  let clone = ptr.clone();
+  assert_eq!(clone.strong_count(), 2);
+}
+
+#[test]
+fn empty_smart_pointer_is_a_placeholder() {
+  let mut ptr = SmartPtr::<String>::empty();
+  assert!(ptr.is_empty());
+  assert!(!ptr.valid());
+  assert_eq!(ptr.strong_count(), 0);
+
+  let empty_cpy = ptr.clone();
+  assert!(empty_cpy.is_empty());
+  assert!(ptr == empty_cpy);
+  assert_eq!(format!("{:?}", empty_cpy), "SmartPtr(null)");
+  assert_eq!(format!("{:>8}", empty_cpy), "  <null>");
+  drop(empty_cpy);
+
+  ptr.get_or_init(|| String::from("mesh")).push_str("es");
+  assert!(!ptr.is_empty());
+  assert_eq!(ptr.access(), "meshes");
+  assert!(ptr != SmartPtr::empty());
+
+  let ptr_cpy = ptr.clone();
+  ptr.get_or_init(|| unreachable!());
+  assert!(ptr.ptr_eq(&ptr_cpy));
+
+  ptr.set(String::from("texture"));
+  assert!(!ptr.ptr_eq(&ptr_cpy));
+  assert_eq!(ptr_cpy.strong_count(), 1);
+  assert_eq!(ptr.to_string(), "texture");
+}
+
+#[test]
+fn smart_pointer_compares_against_values() {
+  let smart = SmartPtr::new(42usize);
+  assert_eq!(smart, 42usize);
+  assert_eq!(42usize, smart);
+  assert_ne!(smart, 7);
+  let answer: &usize = &42;
+  assert!(smart == answer);
+
+  let default_config = SmartPtr::new(String::from("release"));
+  let config_ptr = SmartPtr::new(String::from("release"));
+  assert!(config_ptr == *default_config);
+  let release = &String::from("release");
+  assert!(config_ptr == release);
+
+  let name: SmartPtr<str> = SmartPtr::from_box(Box::from("mesh"));
+  assert!(name == "mesh");
+  assert!(name != "texture");
+
+  let empty = SmartPtr::<usize>::empty();
+  assert_ne!(empty, 0usize);
+  assert_ne!(0usize, empty);
+  assert!(empty != answer);
+}
+
+#[test]
+fn smart_pointers_order_by_value() {
+  use std::collections::BinaryHeap;
+
+  let values = [5, -2, 9, 0, 3];
+  let mut ptrs: Vec<SmartPtr<i32>> = values.iter().copied().map(SmartPtr::new).collect();
+  ptrs.push(SmartPtr::empty());
+  ptrs.sort();
+
+  assert!(ptrs[0].is_empty());
+  assert!(ptrs[1..].iter().map(|ptr| *ptr.access()).eq([-2, 0, 3, 5, 9]));
+
+  let mut heap: BinaryHeap<SmartPtr<i32>> = values.iter().copied().map(SmartPtr::new).collect();
+  let mut popped = Vec::new();
+  while let Some(ptr) = heap.pop() {
+    popped.push(*ptr);
+  }
+  assert_eq!(popped, [9, 5, 3, 0, -2]);
+
+  assert!(SmartPtr::new(1.5f64) > SmartPtr::empty());
+  assert_eq!(SmartPtr::new(f64::NAN).partial_cmp(&SmartPtr::new(0.0)), None);
+}
+
+#[test]
+fn smart_pointers_hash_by_value() {
+  use std::collections::HashMap;
+
+  let mut meshes = HashMap::new();
+  meshes.insert(SmartPtr::new(String::from("cube")), 8);
+  meshes.insert(SmartPtr::empty(), 0);
+
+  assert_eq!(meshes.get(&SmartPtr::new(String::from("cube"))), Some(&8));
+  assert_eq!(meshes.get(&SmartPtr::empty()), Some(&0));
+  assert_eq!(meshes.get(&SmartPtr::new(String::from("sphere"))), None);
+}
+
+#[test]
+fn smart_pointer_keys_borrow_their_value() {
+  use std::borrow::BorrowMut;
+  use std::collections::{BTreeSet, HashMap};
+
+  let mut shaders = HashMap::new();
+  shaders.insert(SmartPtr::new(String::from("key")), 1);
+  assert_eq!(shaders.get("key"), Some(&1));
+  assert_eq!(shaders.get(&String::from("key")), Some(&1));
+  assert_eq!(shaders.get("missing"), None);
+
+  let mut layers = BTreeSet::new();
+  layers.insert(SmartPtr::new(vec![1, 2]));
+  assert!(layers.contains([1, 2].as_slice()));
+
+  let mut ptr = SmartPtr::new(String::from("mesh"));
+  BorrowMut::<String>::borrow_mut(&mut ptr).push('!');
+  assert_eq!(ptr.access(), "mesh!");
+}
+
+#[test]
+fn smart_pointer_converts_from_values() {
+  struct Mesh {
+    vertices: usize,
+  }
+
+  fn vertex_count(mesh: impl Into<SmartPtr<Mesh>>) -> usize {
+    mesh.into().vertices
+  }
+
+  let shared = SmartPtr::new(Mesh { vertices: 8 });
+  assert_eq!(vertex_count(Mesh { vertices: 3 }), 3);
+  assert_eq!(vertex_count(shared.clone()), 8);
+  assert_eq!(vertex_count(Box::new(Mesh { vertices: 4 })), 4);
+  assert_eq!(shared.strong_count(), 1);
+
+  let ptr: SmartPtr<usize> = 5.into();
+  assert_eq!(ptr, 5);
+}
+
+#[test]
+fn smart_projection_keeps_parent_alive() {
+  use std::cell::Cell;
+
+  struct Transform {
+    position: [f32; 3],
+  }
+
+  struct Entity<'a> {
+    transform: Transform,
+    drops: &'a Cell<usize>,
+  }
+
+  impl Drop for Entity<'_> {
+    fn drop(&mut self) {
+      self.drops.set(self.drops.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+  let entity = SmartPtr::new(Entity {
+    transform: Transform {
+      position: [1.0, 2.0, 3.0],
+    },
+    drops: &drops,
+  });
+
+  let mut position = entity.project(|entity| &mut entity.transform.position);
+  assert_eq!(entity.strong_count(), 2);
+  drop(entity);
+  assert_eq!(drops.get(), 0);
+
+  position[1] = 5.0;
+  assert_eq!(*position, [1.0, 5.0, 3.0]);
+  assert_eq!(position.parent().transform.position[1], 5.0);
+
+  let position_cpy = position.clone();
+  drop(position);
+  assert_eq!(position_cpy[0], 1.0);
+  assert_eq!(drops.get(), 0);
+
+  drop(position_cpy);
+  assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn pointers_map_into_fields() {
+  struct Material {
+    albedo: [f32; 3],
+  }
+
+  struct Mesh {
+    material: Material,
+  }
+
+  let mut mesh = Mesh {
+    material: Material { albedo: [0.5; 3] },
+  };
+
+  let mut albedo = MutPtr::new(&mut mesh).map_mut(|mesh| &mut mesh.material.albedo);
+  albedo[2] = 1.0;
+  assert_eq!(mesh.material.albedo, [0.5, 0.5, 1.0]);
+
+  let material = ConstPtr::new(&mesh).map(|mesh| &mesh.material);
+  assert_eq!(*material.map(|material| &material.albedo[2]), 1.0);
+
+  let mut called = false;
+  let null = ConstPtr::<Mesh>::default().map(|mesh| {
+    called = true;
+    &mesh.material
+  });
+  assert!(null.null());
+  assert!(MutPtr::<Mesh>::default().map_mut(|mesh| &mut mesh.material).null());
+  assert!(!called);
+}
+
+#[test]
+fn pointer_addresses_keep_provenance() {
+  let mut values = [10u32, 20, 30];
+  let base = MutPtr::from_slice(&mut values).get_mut(0).unwrap();
+
+  let third = base.with_addr(base.addr() + 2 * std::mem::size_of::<u32>());
+  assert_eq!(*third, 30);
+
+  let mut second = base.map_addr(|addr| addr + std::mem::size_of::<u32>());
+  *second = 25;
+  assert_eq!(values[1], 25);
+
+  let first = ConstPtr::new(&values[0]);
+  assert_eq!(first.addr(), base.addr());
+  assert!(first.addr() % std::mem::align_of::<u32>() == 0);
+
+  let exposed = first.expose_provenance();
+  let rebuilt = ConstPtr::<u32>::with_exposed_provenance(exposed);
+  assert_eq!(*rebuilt, 10);
+  assert_eq!(MutPtr::<u32>::with_exposed_provenance(0).addr(), 0);
+}
+
+#[test]
+fn volatile_access_round_trips() {
+  let mut register = 0u32;
+  let mut ptr = MutPtr::new(&mut register);
+
+  ptr.write_volatile(0xdead_beef);
+  assert_eq!(ptr.read_volatile(), 0xdead_beef);
+  assert_eq!(ConstPtr::from(ptr).read_volatile(), 0xdead_beef);
+  assert_eq!(register, 0xdead_beef);
+}
+
+#[test]
+fn lifetime_carrying_pointers() {
+  struct Camera<'a> {
+    target: RefPtr<'a, [f32; 3]>,
+  }
+
+  let mut target = [1.0, 2.0, 3.0];
+  {
+    let mut ptr = RefMutPtr::new(&mut target);
+    ptr[0] = 4.0;
+    ptr.reborrow()[1] = 5.0;
+
+    let shared = RefPtr::from(ptr);
+    let camera = Camera { target: shared };
+    let camera_cpy = Camera { target: camera.target };
+    assert_eq!(*camera.target, [4.0, 5.0, 3.0]);
+    assert_eq!(camera_cpy.target.raw(), camera.target.raw());
+  }
+
+  let erased;
+  {
+    let value = 7u32;
+    erased = RefPtr::new(&value).erase();
+    assert_eq!(*erased, 7);
+  }
+  assert!(erased.present());
+}
+
+#[test]
+fn pointers_initialize_maybe_uninit() {
+  use std::mem::MaybeUninit;
+
+  let mut slots: [MaybeUninit<String>; 3] = [const { MaybeUninit::uninit() }; 3];
+  let mut slice = MutPtr::from_slice(&mut slots);
+  for (i, name) in ["albedo", "normal", "roughness"].into_iter().enumerate() {
+    let slot = slice.get_mut(i).unwrap();
+    let texture = slot.init(String::from(name));
+    assert_eq!(*texture, name);
+  }
+
+  let mut first = MutPtr::from(&mut slots[0]);
+  unsafe { first.assume_init_mut().push('!') };
+  assert_eq!(unsafe { ConstPtr::from(first).assume_init_ref() }, "albedo!");
+
+  let names = slots.map(|slot| unsafe { slot.assume_init() });
+  assert_eq!(names, ["albedo!", "normal", "roughness"]);
+
+  let mut buffer = [MaybeUninit::<u16>::uninit(); 4];
+  let values = MutPtr::from_slice(&mut buffer).write_slice(&[1, 2, 3, 4]);
+  assert_eq!(values.as_ref(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn smart_pointer_emplaces_in_heap() {
+  use std::cell::Cell;
+
+  struct Chunk<'a> {
+    heights: [u8; 64 * 1024],
+    drops: &'a Cell<usize>,
+  }
+
+  impl Drop for Chunk<'_> {
+    fn drop(&mut self) {
+      self.drops.set(self.drops.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+
+  let chunk = unsafe {
+    SmartPtr::<Chunk>::emplace(|slot| {
+      let raw = slot.as_mut_ptr();
+      std::ptr::addr_of_mut!((*raw).heights).write_bytes(3, 1);
+      std::ptr::addr_of_mut!((*raw).drops).write(&drops);
+    })
+  };
+  assert!(chunk.heights.iter().all(|&height| height == 3));
+  drop(chunk);
+  assert_eq!(drops.get(), 1);
+
+  let uninit = SmartPtr::<Chunk>::new_uninit();
+  let uninit_cpy = uninit.clone();
+  drop(uninit_cpy);
+  drop(uninit);
+  assert_eq!(drops.get(), 1);
+
+  let mut slot = SmartPtr::<u32>::new_uninit();
+  slot.access_mut().write(9);
+  let value = unsafe { slot.assume_init() };
+  assert_eq!(value, 9);
+  assert_eq!(value.strong_count(), 1);
+}
+
+#[test]
+fn smart_pointer_builds_cyclic_values() {
+  struct Node {
+    this: WeakPtr<Node>,
+    value: usize,
+  }
+
+  let node = SmartPtr::new_cyclic(|this| {
+    assert!(this.upgrade().is_none());
+    Node {
+      this: this.clone(),
+      value: 3,
+    }
+  });
+
+  let upgraded = node.this.upgrade().unwrap();
+  assert!(upgraded.ptr_eq(&node));
+  assert_eq!(upgraded.value, 3);
+  assert_eq!(node.strong_count(), 2);
+  drop(upgraded);
+
+  let weak = node.this.clone();
+  drop(node);
+  assert!(weak.upgrade().is_none());
+
+  let unwound = std::panic::catch_unwind(|| {
+    SmartPtr::<Node>::new_cyclic(|_| panic!("construction failed"));
+  });
+  assert!(unwound.is_err());
+}
+
+#[test]
+fn smart_pointer_shares_slices_from_vec() {
+  use std::cell::Cell;
+
+  struct Index<'a>(u32, &'a Cell<usize>);
+
+  impl Drop for Index<'_> {
+    fn drop(&mut self) {
+      self.1.set(self.1.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+  let indices: SmartPtr<[Index]> = (0..6).map(|i| Index(i, &drops)).collect::<Vec<_>>().into();
+  let draw_call = indices.clone();
+
+  assert_eq!(indices.len(), 6);
+  assert_eq!(draw_call[4].0, 4);
+  assert_eq!(draw_call.iter().map(|index| index.0).sum::<u32>(), 15);
+
+  drop(indices);
+  assert_eq!(drops.get(), 0);
+  drop(draw_call);
+  assert_eq!(drops.get(), 6);
+
+  let no_indices = SmartPtr::from_vec(Vec::<u8>::new());
+  assert!(no_indices.access().is_empty());
+  assert!(no_indices.valid());
+}
+
+#[test]
+fn smart_pointer_shares_strings() {
+  use std::collections::HashMap;
+
+  let name: SmartPtr<str> = String::from("terrain").into();
+  let name_cpy = name.clone();
+  assert!(name_cpy.ptr_eq(&name));
+  assert!(name == "terrain");
+  assert_eq!(name.len(), 7);
+  assert_eq!(format!("{name}/{name_cpy:?}"), "terrain/\"terrain\"");
+
+  let copied = SmartPtr::<str>::from("terrain");
+  assert!(copied == name);
+  assert!(!copied.ptr_eq(&name));
+
+  let mut lods = HashMap::new();
+  lods.insert(name, 4);
+  assert_eq!(lods.get("terrain"), Some(&4));
+  assert_eq!(lods.get(&copied), Some(&4));
+  assert_eq!(name_cpy.strong_count(), 2);
+}
+
+#[test]
+fn slice_pointers_iterate_elements() {
+  let mut weights = [1u32, 2, 3, 4];
+
+  let ptr = ConstPtr::from_slice(&weights);
+  assert_eq!(ptr.iter().sum::<u32>(), 10);
+  assert_eq!(ptr.iter().next_back(), Some(&4));
+  let mut total = 0;
+  for weight in &ptr {
+    total += weight;
+  }
+  assert_eq!(total, 10);
+
+  let mut ptr = MutPtr::from_slice(&mut weights);
+  let mut iter = ptr.iter_mut();
+  assert_eq!(iter.len(), 4);
+  iter.next();
+  assert_eq!(iter.size_hint(), (3, Some(3)));
+  for weight in &mut ptr {
+    *weight *= 2;
+  }
+  assert_eq!((&ptr).into_iter().copied().collect::<Vec<_>>(), [2, 4, 6, 8]);
+  assert_eq!(weights, [2, 4, 6, 8]);
+
+  let mut null = ptr;
+  null.clear();
+  assert_eq!(null.len(), 4);
+  assert_eq!(null.iter_mut().count(), 0);
+  assert_eq!(ConstPtr::from(null).iter().len(), 0);
+}
+
+#[test]
+fn raw_pointer_conversions_keep_metadata() {
+  trait Handler {
+    fn handle(&mut self, event: u32) -> u32;
+  }
+
+  struct Doubler(u32);
+
+  impl Handler for Doubler {
+    fn handle(&mut self, event: u32) -> u32 {
+      self.0 += 1;
+      event * 2
+    }
+  }
+
+  let mut doubler = Doubler(0);
+  let raw: *mut dyn Handler = &mut doubler;
+  let mut handler = MutPtr::from(raw);
+  assert_eq!(handler.handle(4), 8);
+
+  let back: *mut dyn Handler = handler.into();
+  assert!(std::ptr::eq(back, raw));
+  assert_eq!(unsafe { (*back).handle(5) }, 10);
+  assert_eq!(doubler.0, 2);
+
+  let bytes = [1u8, 2, 3];
+  let raw: *const [u8] = &bytes;
+  let ptr = ConstPtr::from(raw);
+  assert_eq!(ptr.len(), 3);
+  assert_eq!(ptr[2], 3);
+
+  let back: *const [u8] = ptr.into();
+  assert_eq!(back.len(), 3);
+  assert_eq!(back, raw);
+}
+
+#[test]
+fn any_pointers_downcast() {
+  use std::any::{Any, TypeId};
+
+  struct Position {
+    x: f32,
+  }
+
+  struct Health {
+    points: u32,
+  }
+
+  let mut position = Position { x: 1.5 };
+  let mut health = Health { points: 100 };
+  let mut components: [MutPtr<dyn Any>; 2] = [
+    MutPtr::from(&mut position as *mut dyn Any),
+    MutPtr::from(&mut health as *mut dyn Any),
+  ];
+
+  assert_eq!(components[0].type_id(), Some(TypeId::of::<Position>()));
+  assert!(components[0].downcast_mut::<Health>().is_none());
+  components[1].downcast_mut::<Health>().unwrap().points -= 10;
+  assert_eq!(health.points, 90);
+
+  let position_ptr = ConstPtr::from(components[0]);
+  assert_eq!(position_ptr.downcast_ref::<Position>().unwrap().x, 1.5);
+  assert!(position_ptr.downcast_ref::<Health>().is_none());
+
+  let mut null = components[1];
+  null.clear();
+  assert_eq!(null.type_id(), None);
+  assert!(null.downcast_mut::<Health>().is_none());
+  assert!(ConstPtr::from(null).downcast_ref::<Health>().is_none());
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn pointers_coerce_to_trait_objects() {
+  trait System {
+    fn run(&self) -> u32;
+  }
+
+  struct Physics(u32);
+
+  impl System for Physics {
+    fn run(&self) -> u32 {
+      self.0 * 2
+    }
+  }
+
+  let mut physics = Physics(4);
+
+  let system: ConstPtr<dyn System> = ConstPtr::new(&physics);
+  assert_eq!(system.run(), 8);
+
+  let mut system: MutPtr<dyn System> = MutPtr::new(&mut physics);
+  assert_eq!(system.as_opt_mut().unwrap().run(), 8);
+
+  let systems: Vec<SmartPtr<dyn System>> = vec![SmartPtr::new(Physics(1)), SmartPtr::new(Physics(2))];
+  assert_eq!(systems.iter().map(|system| system.run()).sum::<u32>(), 6);
+}
+
+#[test]
+fn null_aware_combinators() {
+  let mut calls = 0;
+  let mut health = 80u32;
+
+  let ptr = ConstPtr::new(&health);
+  assert_eq!(ptr.map_present(|health| health * 2), Some(160));
+  let alive = ptr.inspect(|_| calls += 1).filter(|&health| health > 0);
+  assert!(alive.present());
+  assert!(ptr.filter(|&health| health > 100).null());
+
+  let null = ConstPtr::<u32>::default();
+  assert_eq!(null.map_present(|_| calls += 1), None);
+  assert!(null.inspect(|_| calls += 1).filter(|_| true).null());
+  assert_eq!(calls, 1);
+  assert_eq!(*null.or(ptr), 80);
+  assert!(ptr.or(null) == ptr);
+
+  let mut_ptr = MutPtr::new(&mut health);
+  assert_eq!(
+    mut_ptr.filter(|&health| health < 100).with_mut(|health| {
+      *health -= 30;
+      *health
+    }),
+    Some(50)
+  );
+  assert_eq!(MutPtr::<u32>::default().with_mut(|_| calls += 1), None);
+  assert_eq!(*MutPtr::<u32>::default().or(mut_ptr), 50);
+  assert_eq!(calls, 1);
+}
+
+#[test]
+fn option_reference_conversions() {
+  let mut lights = [3u32, 7];
+
+  let found = ConstPtr::from(lights.iter().find(|&&light| light > 5));
+  assert_eq!(found.to_option_copied(), Some(7));
+  assert_eq!(unsafe { found.into_opt() }, Some(&7));
+
+  let missing = ConstPtr::from(lights.iter().find(|&&light| light > 10));
+  assert!(missing.null());
+  assert_eq!(missing.to_option_copied(), None);
+  assert_eq!(unsafe { missing.into_opt() }, None);
+
+  let first = MutPtr::from(lights.first_mut());
+  *unsafe { first.into_opt_mut() }.unwrap() += 1;
+  assert_eq!(first.to_option_copied(), Some(4));
+  assert_eq!(unsafe { first.into_opt() }, Some(&4));
+
+  let none = MutPtr::<u32>::from(None);
+  assert_eq!(unsafe { none.into_opt_mut() }, None);
+  assert_eq!(none.to_option_copied(), None);
+}
+
+mod const_pointers {
+  use super::*;
+
+  struct Handler {
+    id: u32,
+  }
+
+  const FIRST: Handler = Handler { id: 1 };
+
+  const HANDLERS: [ConstPtr<Handler>; 3] = [
+    ConstPtr::from_ref(&FIRST),
+    ConstPtr::from_ref(&Handler { id: 2 }),
+    ConstPtr::null_ptr(),
+  ];
+
+  const NULL_MUT: MutPtr<u32> = MutPtr::null_ptr();
+  const PRESENT: bool = HANDLERS[0].present() && NULL_MUT.null() && HANDLERS[2].raw().is_null();
+
+  static VTABLE: [SyncPtr<Handler>; 2] = unsafe { [SyncPtr::new(HANDLERS[0]), SyncPtr::new(HANDLERS[2])] };
+
+  #[test]
+  fn pointers_built_in_const_items() {
+    const { assert!(PRESENT) };
+
+    assert_eq!(
+      HANDLERS
+        .iter()
+        .filter_map(|handler| handler.as_opt())
+        .map(|handler| handler.id)
+        .sum::<u32>(),
+      3
+    );
+    assert_eq!(VTABLE[0].id, 1);
+    assert!(VTABLE[1].null());
+
+    let mut counter = 0u32;
+    let mut ptr = MutPtr::from_mut(&mut counter);
+    *ptr += 1;
+    assert_eq!(counter, 1);
+  }
+}
+
+#[test]
+fn smart_pointer_alternate_debug_shows_count() {
+  let ptr = SmartPtr::new(7usize);
+  let clones = [ptr.clone(), ptr.clone()];
+
+  assert_eq!(format!("{:?}", ptr), "7");
+  assert_eq!(
+    format!("{:#?}", ptr),
+    format!(
+      "SmartPtr {{\n    addr: {:p},\n    strong: 3,\n    value: 7,\n}}",
+      ptr.access()
+    )
+  );
+  drop(clones);
+  assert!(format!("{:#?}", ptr).contains("strong: 1,"));
+
+  let empty = SmartPtr::<usize>::empty();
+  assert_eq!(
+    format!("{:#?}", empty),
+    "SmartPtr {\n    addr: 0x0,\n    strong: 0,\n    value: <invalid>,\n}"
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn shared_serialization_preserves_sharing() {
+  use crate::serde_shared;
+
+  #[derive(serde::Serialize, serde::Deserialize)]
+  struct Texture {
+    path: String,
+  }
+
+  #[derive(serde::Serialize, serde::Deserialize)]
+  struct Scene {
+    #[serde(with = "serde_shared")]
+    floor: SmartPtr<Texture>,
+    #[serde(with = "serde_shared")]
+    walls: SmartPtr<Texture>,
+    #[serde(with = "serde_shared")]
+    ceiling: SmartPtr<Texture>,
+    #[serde(with = "serde_shared")]
+    sky: SmartPtr<Texture>,
+  }
+
+  let bricks = SmartPtr::new(Texture {
+    path: String::from("bricks.png"),
+  });
+  let scene = Scene {
+    floor: bricks.clone(),
+    walls: bricks.clone(),
+    ceiling: bricks,
+    sky: SmartPtr::new(Texture {
+      path: String::from("sky.png"),
+    }),
+  };
+
+  let json = serde_shared::with_session(|| serde_json::to_string(&scene)).unwrap();
+  assert_eq!(
+    json,
+    r#"{"floor":[0,{"path":"bricks.png"}],"walls":[0,null],"ceiling":[0,null],"sky":[1,{"path":"sky.png"}]}"#
+  );
+
+  let scene: Scene = serde_shared::with_session(|| serde_json::from_str(&json)).unwrap();
+  assert!(scene.floor.ptr_eq(&scene.walls));
+  assert!(scene.floor.ptr_eq(&scene.ceiling));
+  assert!(!scene.floor.ptr_eq(&scene.sky));
+  assert_eq!(scene.floor.strong_count(), 3);
+  assert_eq!(scene.sky.path, "sky.png");
+
+  assert!(serde_json::to_string(&scene).is_err());
+  let dangling = r#"{"floor":[0,null],"walls":[0,null],"ceiling":[0,null],"sky":[1,{"path":"sky.png"}]}"#;
+  assert!(serde_shared::with_session(|| serde_json::from_str::<Scene>(dangling)).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn shared_serialization_rejects_cycles() {
+  use crate::serde_shared;
+
+  #[derive(serde::Serialize)]
+  struct Node {
+    #[serde(with = "serde_shared")]
+    next: SmartPtr<Node>,
+  }
+
+  let mut node = SmartPtr::new(Node { next: SmartPtr::empty() });
+  let cycle = node.clone();
+  node.next = cycle;
+
+  let err = serde_shared::with_session(|| serde_json::to_string(&node.next)).unwrap_err();
+  assert!(err.to_string().contains("reachable from its own value"));
+
+  node.next = SmartPtr::empty();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_into_existing_smart_pointer() {
+  use serde::de::DeserializeSeed;
+
+  #[derive(serde::Deserialize)]
+  struct Config {
+    vsync: bool,
+    fov: u32,
+  }
+
+  let config = SmartPtr::new(Config { vsync: false, fov: 60 });
+  let renderer_config = config.clone();
+
+  let mut deserializer = serde_json::Deserializer::from_str(r#"{"vsync":true,"fov":90}"#);
+  config.deserialize_in_place(&mut deserializer).unwrap();
+  assert!(renderer_config.vsync);
+  assert_eq!(renderer_config.fov, 90);
+  assert!(renderer_config.ptr_eq(&config));
+
+  let mut deserializer = serde_json::Deserializer::from_str(r#"{"vsync":false,"fov":75}"#);
+  SmartPtrSeed(&config).deserialize(&mut deserializer).unwrap();
+  assert_eq!(renderer_config.fov, 75);
+
+  let mut deserializer = serde_json::Deserializer::from_str(r#"{"vsync":true}"#);
+  assert!(config.deserialize_in_place(&mut deserializer).is_err());
+  assert_eq!(renderer_config.fov, 75);
+
+  let mut deserializer = serde_json::Deserializer::from_str(r#"{"vsync":true,"fov":90}"#);
+  assert!(SmartPtr::<Config>::empty().deserialize_in_place(&mut deserializer).is_err());
+}
+
+#[test]
+fn mut_pointer_takes_values() {
+  let mut slot = String::from("pending");
+  let mut ptr = MutPtr::new(&mut slot);
+
+  assert_eq!(ptr.take_if(|name| name.is_empty()), None);
+  let taken = ptr.take();
+  assert_eq!(taken, "pending");
+  assert!(ptr.is_empty());
+  assert_eq!(ptr.take_if(|name| name.is_empty()), Some(String::new()));
+  assert!(slot.is_empty());
+
+  let mut null = MutPtr::<String>::default();
+  assert_eq!(null.take_if(|_| true), None);
+  let result = std::panic::catch_unwind(move || null.take());
+  assert!(result.is_err());
+}
+
+#[test]
+fn mut_pointer_mutation_helpers() {
+  use std::cell::Cell;
+
+  struct Tracked<'a>(u32, &'a Cell<usize>);
+
+  impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+      self.1.set(self.1.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+  let mut slot = Tracked(1, &drops);
+  let mut ptr = MutPtr::new(&mut slot);
+
+  assert!(ptr.set(Tracked(2, &drops)));
+  assert_eq!(drops.get(), 1);
+  ptr.update(|tracked| tracked.0 += 1);
+  assert_eq!(ptr.update_or(|tracked| tracked.0 * 10, 0), 30);
+  assert_eq!(slot.0, 3);
+
+  let mut null = MutPtr::<Tracked>::default();
+  assert!(!null.set(Tracked(4, &drops)));
+  assert_eq!(drops.get(), 2);
+  null.update(|_| unreachable!());
+  assert_eq!(null.update_or(|tracked| tracked.0, 7), 7);
+
+  drop(slot);
+  assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn pointers_alias_container_contents() {
+  use std::cell::RefCell;
+  use std::rc::Rc;
+  use std::sync::Arc;
+
+  let shared = Rc::new(5u32);
+  let ptr = ConstPtr::from(&shared);
+  assert_eq!(ptr.raw(), Rc::as_ptr(&shared));
+  assert_eq!(Rc::strong_count(&shared), 1);
+  assert_eq!(*ptr, 5);
+
+  let atomic = Arc::new(String::from("mesh"));
+  let ptr = ConstPtr::from(&atomic);
+  assert_eq!(ptr.raw(), Arc::as_ptr(&atomic));
+  assert_eq!(ptr.as_str(), "mesh");
+
+  let cell = Rc::new(RefCell::new(1u32));
+  let mut ptr = MutPtr::from(&cell);
+  *ptr += 1;
+  assert_eq!(*cell.borrow(), 2);
+
+  let mut boxed = Box::new(3u32);
+  let mut ptr = MutPtr::from(&mut boxed);
+  *ptr += 1;
+  assert_eq!(ConstPtr::from(&boxed).raw(), ptr.raw().cast_const());
+  assert_eq!(*boxed, 4);
+
+  let mut indices = vec![1u16, 2, 3];
+  assert_eq!(ConstPtr::from(&indices).raw().cast::<u16>(), indices.as_ptr());
+  let mut ptr = MutPtr::from(&mut indices);
+  ptr[1] = 20;
+  assert_eq!(ptr.len(), 3);
+  assert_eq!(indices, [1, 20, 3]);
+}
+
+#[test]
+fn smart_pool_recycles_slots() {
+  use std::cell::Cell;
+
+  struct Counted<'a>(&'a Cell<usize>);
+
+  impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+
+  let drops = Cell::new(0);
+  let pool = SmartPool::with_chunk_size(32);
+
+  let mut first_frame = Vec::new();
+  for frame in 0..100 {
+    let entities = (0..32).map(|_| pool.alloc(Counted(&drops))).collect::<Vec<_>>();
+    let addrs = entities.iter().map(|e| e.as_const_ptr().raw()).collect::<Vec<_>>();
+
+    assert_eq!(pool.live(), 32);
+    if frame == 0 {
+      first_frame = addrs;
+    } else {
+      assert_eq!(addrs.len(), first_frame.len());
+      assert!(addrs.iter().all(|addr| first_frame.contains(addr)));
+    }
+  }
+
+  assert_eq!(pool.live(), 0);
+  assert_eq!(pool.capacity(), 32);
+  assert_eq!(drops.get(), 3200);
+}
+
+#[test]
+fn smart_pool_outlived_by_pointers() {
+  let pool = SmartPool::new();
+  let ptr = pool.alloc(String::from("orc"));
+  let weak = ptr.downgrade();
+  drop(pool);
+
+  assert_eq!(ptr.as_str(), "orc");
+  let ptr2 = ptr.clone();
+  drop(ptr);
+  assert_eq!(*ptr2, "orc");
+  drop(ptr2);
+  assert!(weak.upgrade().is_none());
+
+  let pool = SmartPool::with_chunk_size(1);
+  let ptr = pool.alloc(7u32);
+  let weak = ptr.downgrade();
+  drop(ptr);
+  assert_eq!(pool.live(), 1);
+  drop(weak);
+  assert_eq!(pool.live(), 0);
+}
+
+#[test]
+fn smart_pointer_address_stable_across_clones() {
+  #[repr(align(64))]
+  struct Aligned(u8);
+
+  let ptr = SmartPtr::new(Aligned(3));
+  let raw = ptr.as_const_ptr().raw();
+  assert!(raw.is_aligned());
+
+  let clone = ptr.clone();
+  let weak = clone.downgrade();
+  assert_eq!(clone.as_const_ptr().raw(), raw);
+  assert_eq!(weak.upgrade().unwrap().as_const_ptr().raw(), raw);
+  drop(ptr);
+  assert_eq!(clone.0, 3);
+}
+
+#[test]
+fn smart_pointer_zero_sized_values() {
+  use std::cell::Cell;
+
+  let unit = SmartPtr::new(());
+  assert!(unit.valid());
+  assert_eq!(unit.access(), &());
+  let clone = unit.clone();
+  assert_eq!(unit.strong_count(), 2);
+  drop(unit);
+  assert!(clone.valid());
+  assert_eq!(clone.into_inner(), Some(()));
+
+  thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+  }
+
+  struct Marker;
+
+  impl Drop for Marker {
+    fn drop(&mut self) {
+      DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+  }
+
+  let marker = SmartPtr::new(Marker);
+  let weak = marker.downgrade();
+  let clones = vec![marker.clone(), marker.clone(), marker];
+  assert_eq!(clones[0].strong_count(), 3);
+  drop(clones);
+  assert_eq!(DROPS.with(Cell::get), 1);
+  assert!(weak.upgrade().is_none());
+
+  let boxed = SmartPtr::from_box(Box::new(Marker));
+  drop(boxed.clone());
+  drop(boxed);
+  assert_eq!(DROPS.with(Cell::get), 2);
+}
+
+#[test]
+fn smart_pointer_releases_allocations_when_drop_panics() {
+  use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+  };
+
+  struct Counting {
+    allocs: AtomicUsize,
+    deallocs: AtomicUsize,
+  }
+
+  unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      self.allocs.fetch_add(1, Ordering::Relaxed);
+      System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      self.deallocs.fetch_add(1, Ordering::Relaxed);
+      System.dealloc(ptr, layout)
+    }
+  }
+
+  static COUNTING: Counting = Counting {
+    allocs: AtomicUsize::new(0),
+    deallocs: AtomicUsize::new(0),
+  };
+
+  struct Bomb(u64);
+
+  impl Drop for Bomb {
+    fn drop(&mut self) {
+      panic!("bomb {} dropped", self.0);
+    }
+  }
+
+  let ptr = SmartPtr::new_in(Bomb(1), &COUNTING);
+  let clone = ptr.clone();
+  drop(ptr);
+  assert!(catch_unwind(AssertUnwindSafe(|| drop(clone))).is_err());
+  assert_eq!(COUNTING.allocs.load(Ordering::Relaxed), 2);
+  assert_eq!(COUNTING.deallocs.load(Ordering::Relaxed), 2);
+
+  let ptr = SmartPtr::new(Bomb(2));
+  let weak = ptr.downgrade();
+  assert!(catch_unwind(AssertUnwindSafe(|| drop(ptr))).is_err());
+  assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn smart_pointer_leaks_value() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+  struct Registry(Vec<&'static str>);
+
+  impl Drop for Registry {
+    fn drop(&mut self) {
+      DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  let ptr = SmartPtr::new(Registry(vec!["mesh"]));
+  let clone = ptr.clone();
+  let weak = ptr.downgrade();
+  let mut registry = ptr.leak();
+  (*registry).0.push("texture");
+
+  assert_eq!(clone.strong_count(), 2);
+  drop(clone);
+  assert_eq!(weak.upgrade().unwrap().0, ["mesh", "texture"]);
+  drop(weak);
+
+  assert_eq!((*registry).0, ["mesh", "texture"]);
+  assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn smart_pointer_views_do_not_own() {
+  use std::cell::Cell;
+
+  struct Flagged<'a>(&'a Cell<bool>);
+
+  impl Drop for Flagged<'_> {
+    fn drop(&mut self) {
+      self.0.set(true);
+    }
+  }
+
+  let dropped = Cell::new(false);
+  let mut ptr = SmartPtr::new(Flagged(&dropped));
+  let view = ConstPtr::from(&ptr);
+  let view_mut = MutPtr::from(&mut ptr);
+  assert_eq!(view.raw(), ptr::from_ref(ptr.access()));
+  assert_eq!(view_mut.raw().cast_const(), view.raw());
+  assert_eq!(ptr.strong_count(), 1);
+
+  let clone = ptr.clone();
+  drop(ptr);
+  assert!(!dropped.get());
+  drop(clone);
+  assert!(dropped.get());
+}
+
+#[test]
+fn pointers_from_ref_cell_fields() {
+  use std::cell::RefCell;
+
+  struct Scene {
+    frame: RefCell<u64>,
+    name: RefCell<String>,
+  }
+
+  let scene = Scene {
+    frame: RefCell::new(1),
+    name: RefCell::new(String::from("intro")),
+  };
+
+  let mut frame = MutPtr::from(&scene.frame);
+  *frame += 1;
+  assert_eq!(*scene.frame.borrow(), 2);
+
+  let mut name = MutPtr::from(&scene.name);
+  name.push_str("_cut");
+  let view = ConstPtr::from(&scene.name);
+  assert_eq!(view.as_str(), "intro_cut");
+  assert_eq!(*scene.name.borrow(), "intro_cut");
+}
+
+#[test]
+fn const_pointer_reads_shared_arc() {
+  use std::sync::{Arc, Mutex};
+
+  struct Config {
+    threads: usize,
+  }
+
+  let config = Arc::new(Config { threads: 8 });
+  let worker = {
+    let config = Arc::clone(&config);
+    std::thread::spawn(move || config.threads)
+  };
+
+  let ptr = ConstPtr::from(&config);
+  assert_eq!(ptr.threads, 8);
+  assert_eq!(worker.join().unwrap(), 8);
+  assert_eq!(Arc::strong_count(&config), 1);
+
+  let counter = Arc::new(Mutex::new(0u32));
+  let ptr = ConstPtr::from(&counter);
+  *ptr.lock().unwrap() += 1;
+  assert_eq!(*counter.lock().unwrap(), 1);
+}
+
+#[test]
+fn const_pointer_matches_arc_address() {
+  use std::sync::Arc;
+
+  let text: Arc<str> = Arc::from("shader");
+  let ptr = ConstPtr::from(&text);
+  assert_eq!(ptr.raw(), Arc::as_ptr(&text));
+  assert_eq!(&*ptr, "shader");
+}
+
+#[test]
+fn pointers_round_trip_through_pins() {
+  use std::marker::PhantomPinned;
+  use std::pin::Pin;
+
+  struct Node {
+    value: u32,
+    _pinned: PhantomPinned,
+  }
+
+  let mut node = Box::pin(Node {
+    value: 1,
+    _pinned: PhantomPinned,
+  });
+
+  let view = ConstPtr::from(node.as_ref());
+  assert_eq!(view.raw(), ptr::from_ref(&*node));
+
+  let mut ptr = unsafe { MutPtr::from_pin_mut(node.as_mut()) };
+  ptr.value += 1;
+  assert_eq!(node.value, 2);
+
+  let pinned: Pin<&Node> = unsafe { view.as_pin_ref() };
+  assert_eq!(pinned.value, 2);
+  assert!(ptr::eq(pinned.get_ref(), node.as_ref().get_ref()));
+}
+
+#[test]
+#[should_panic(expected = "as_pin_ref on a null ConstPtr")]
+fn null_pointer_as_pin_ref_panics() {
+  let _ = unsafe { ConstPtr::<u32>::default().as_pin_ref() };
+}
+
+#[test]
+fn pointers_from_cells() {
+  use std::cell::{Cell, UnsafeCell};
+
+  let hits = Cell::new(1u32);
+  let mut ptr = MutPtr::from(&hits);
+  ptr.write(5);
+  assert_eq!(hits.get(), 5);
+  hits.set(6);
+  assert_eq!(*ConstPtr::from(&hits), 6);
+
+  let slot = UnsafeCell::new(String::from("idle"));
+  let mut ptr = MutPtr::from(&slot);
+  ptr.push_str("_walk");
+  assert_eq!(ConstPtr::from(&slot).as_str(), "idle_walk");
+  assert_eq!(slot.into_inner(), "idle_walk");
+}
+
+#[test]
+fn atomic_pointer_compare_exchange_across_threads() {
+  use std::sync::atomic::Ordering;
+
+  let mut scenes = [0u32, 1, 2, 3];
+  let candidates = scenes.iter_mut().map(MutPtr::new).collect::<Vec<_>>();
+  let current = AtomicMutPtr::<u32>::default();
+  assert!(current.load(Ordering::Acquire).null());
+
+  std::thread::scope(|scope| {
+    for candidate in &candidates {
+      let candidate = unsafe { SendPtr::new(*candidate) };
+      let current = &current;
+      scope.spawn(move || {
+        let candidate = candidate.into_inner();
+        for _ in 0..1000 {
+          let seen = current.load(Ordering::Acquire);
+          let _ = current.compare_exchange(seen, candidate, Ordering::AcqRel, Ordering::Acquire);
+        }
+      });
+    }
+  });
+
+  let last = current.into_inner();
+  assert!(candidates.contains(&last));
+  assert_eq!(last, candidates[*last as usize]);
+
+  let view = AtomicConstPtr::new(ConstPtr::from(candidates[0]));
+  assert_eq!(
+    view.swap(ConstPtr::from(candidates[1]), Ordering::AcqRel),
+    candidates[0].into()
+  );
+  assert_eq!(*view.load(Ordering::Acquire), 1);
+}
+
+#[test]
+fn atomic_smart_pointer_swaps_under_readers() {
+  use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+  static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+  struct Scene {
+    id: usize,
+    checksum: usize,
+  }
+
+  impl Drop for Scene {
+    fn drop(&mut self) {
+      DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  let current = AtomicSmartPtr::new(SyncSmartPtr::new(Scene { id: 0, checksum: 0 }));
+  let done = AtomicBool::new(false);
+
+  std::thread::scope(|scope| {
+    for _ in 0..4 {
+      scope.spawn(|| {
+        while !done.load(Ordering::Acquire) {
+          let scene = current.load();
+          assert_eq!(scene.checksum, scene.id * 7);
+        }
+      });
+    }
+
+    for id in 1..=200 {
+      current.store(SyncSmartPtr::new(Scene { id, checksum: id * 7 }));
+    }
+    done.store(true, Ordering::Release);
+  });
+
+  assert_eq!(DROPS.load(Ordering::Relaxed), 200);
+  let last = current.into_inner();
+  assert_eq!(last.id, 200);
+  assert_eq!(last.strong_count(), 1);
+}
+
+#[test]
+fn smart_pointer_drop_hook_runs_once() {
+  use std::cell::{Cell, RefCell};
+  use std::rc::Rc;
+
+  let recycled = Rc::new(RefCell::new(Vec::new()));
+
+  let hook_target = Rc::clone(&recycled);
+  let texture = SmartPtr::with_drop_hook(7u32, move |handle| hook_target.borrow_mut().push(*handle));
+  let clones = vec![texture.clone(), texture.clone()];
+  drop(texture);
+  assert!(recycled.borrow().is_empty());
+  drop(clones);
+  assert_eq!(*recycled.borrow(), [7]);
+
+  let runs = Rc::new(Cell::new(0));
+  let counter = Rc::clone(&runs);
+  let leaked = SmartPtr::with_drop_hook(String::from("atlas"), move |_| counter.set(counter.get() + 1));
+  drop(leaked.clone());
+  leaked.leak();
+
+  let counter = Rc::clone(&runs);
+  let unwrapped = SmartPtr::with_drop_hook(1u8, move |_| counter.set(counter.get() + 1));
+  assert_eq!(unwrapped.into_inner(), Some(1));
+  assert_eq!(runs.get(), 0);
+  assert_eq!(Rc::strong_count(&runs), 2);
+}
+
+#[test]
+fn smart_pointer_drop_hook_panic_still_drops_the_value() {
+  use std::rc::Rc;
+
+  let alive = Rc::new(());
+  let texture = SmartPtr::with_drop_hook(Rc::clone(&alive), |_| panic!("hook failed"));
+
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(texture)));
+  assert!(result.is_err());
+  assert_eq!(Rc::strong_count(&alive), 1);
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn smart_pointer_records_live_origins() {
+  fn spawn_player() -> (SmartPtr<&'static str>, u32) {
+    (SmartPtr::new("player"), line!())
+  }
+
+  fn spawn_enemy() -> (SmartPtr<&'static str>, u32) {
+    (SmartPtr::new("enemy"), line!())
+  }
+
+  let (player, player_line) = spawn_player();
+  let (enemy, enemy_line) = spawn_enemy();
+  let enemy_origin = enemy.origin().unwrap();
+  drop(enemy);
+
+  let origin = player.origin().unwrap();
+  assert_eq!(origin.file(), file!());
+  assert_eq!(origin.line(), player_line);
+  assert_eq!(enemy_origin.line(), enemy_line);
+
+  let live = diagnostics::live_origins();
+  assert!(live.contains(&origin));
+  assert!(!live.contains(&enemy_origin));
+  assert!(player.clone().origin().is_some_and(|clone| clone == origin));
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn smart_pointer_conversions_record_the_caller() {
+  let (number, number_line) = (SmartPtr::from(1), line!());
+  let (text, text_line) = (SmartPtr::<str>::from(String::from("text")), line!());
+  let (items, items_line) = (SmartPtr::from_vec(vec![1, 2]), line!());
+  let (fallback, fallback_line) = (SmartPtr::<u32>::default(), line!());
+
+  for (origin, line) in [
+    (number.origin(), number_line),
+    (text.origin(), text_line),
+    (items.origin(), items_line),
+    (fallback.origin(), fallback_line),
+  ] {
+    let origin = origin.unwrap();
+    assert_eq!(origin.file(), file!());
+    assert_eq!(origin.line(), line);
+  }
+}
+
+// the access patterns `cargo miri test` checks against stacked borrows, kept small so they finish quickly under it
+mod stacked_borrows {
+  use super::*;
+
+  #[test]
+  fn clones_interleave_access() {
+    let mut first = SmartPtr::new(1u32);
+    let mut second = first.clone();
+
+    *first.access_mut() += 1;
+    *second.access_mut() += 1;
+    let read = first.access();
+    assert_eq!(*second.access(), 3);
+    assert_eq!(*read, 3);
+  }
+
+  #[test]
+  fn references_survive_count_updates() {
+    let ptr = SmartPtr::new(String::from("mesh"));
+    let value = ptr.access();
+
+    let clone = ptr.clone();
+    let weak = clone.downgrade();
+    drop(clone);
+    drop(weak);
+
+    assert_eq!(value, "mesh");
+  }
+
+  #[test]
+  fn keeps_alive_through_moves() {
+    struct Holder {
+      ptr: SmartPtr<usize>,
+    }
+
+    let holder = {
+      let mut ptr = SmartPtr::new(0usize);
+      *ptr = 1;
+      Holder { ptr: ptr.clone() }
+    };
+
+    assert!(holder.ptr.valid());
+    assert_eq!(*holder.ptr, 1);
+    drop(holder);
+  }
+
+  #[test]
+  fn projections_and_views_share_the_allocation() {
+    let mut ptr = SmartPtr::new((1u8, 2u16));
+    let mut second = ptr.project(|pair| &mut pair.1);
+    *second += 1;
+    ptr.access_mut().0 += 1;
+
+    assert_eq!(*ptr.as_const_ptr(), (2, 3));
+    drop(ptr);
+    assert_eq!(second.parent().access().1, 3);
+  }
+}
+
+#[test]
+fn scoped_pointers_lend_values() {
+  let name = String::from("camera");
+  let len = ConstPtr::scoped(&name, |ptr| ptr.len());
+  assert_eq!(len, 6);
+
+  let mut frames = vec![1u32, 2];
+  MutPtr::scoped(&mut frames, |mut ptr| ptr.push(3));
+  assert_eq!(frames, [1, 2, 3]);
+}
+
+#[cfg(all(feature = "checked", debug_assertions))]
+#[test]
+fn checked_scoped_pointer_escape_panics() {
+  let mut value = 9u32;
+  let escaped = MutPtr::scoped(&mut value, |ptr| {
+    assert_eq!(*ptr, 9);
+    ptr
+  });
+  assert!(std::panic::catch_unwind(|| *escaped).is_err());
+
+  let escaped = ConstPtr::scoped(&value, |ptr| ptr);
+  assert!(std::panic::catch_unwind(|| *escaped).is_err());
+  assert_eq!(*ConstPtr::new(&value), 9);
+}
+
+#[test]
+fn tracked_pointer_sequential_borrows() {
+  let mut health = 10u32;
+  let mut ptr = MutPtr::tracked(&mut health);
+  let copy = ptr;
+
+  {
+    let first = ptr.borrow();
+    let second = copy.borrow();
+    assert_eq!(*first + *second, 20);
+  }
+
+  *ptr.borrow_mut() -= 3;
+  *ptr += 1;
+  assert_eq!(*copy.borrow(), 8);
+  assert_eq!(health, 8);
+}
+
+#[cfg(feature = "borrow-checked")]
+#[test]
+fn tracked_pointer_aliasing_panics() {
+  use std::panic::{catch_unwind, AssertUnwindSafe};
+
+  let mut health = 10u32;
+  let mut ptr = MutPtr::tracked(&mut health);
+  let mut copy = ptr;
+
+  let guard = ptr.borrow_mut();
+  assert!(catch_unwind(AssertUnwindSafe(|| *copy += 1)).is_err());
+  assert!(catch_unwind(AssertUnwindSafe(|| *copy.borrow())).is_err());
+  drop(guard);
+
+  let guard = copy.borrow();
+  assert!(catch_unwind(AssertUnwindSafe(|| drop(ptr.borrow_mut()))).is_err());
+  assert_eq!(*ptr, 10);
+  drop(guard);
+
+  *copy.borrow_mut() += 1;
+  assert_eq!(health, 11);
+}
+
+#[test]
+fn pointers_reject_null_raw_pointers() {
+  assert!(matches!(ConstPtr::<u32>::try_from_raw(ptr::null()), Err(PtrError::Null)));
+  assert!(matches!(MutPtr::<u32>::try_from_raw(ptr::null_mut()), Err(PtrError::Null)));
+  assert!(NonNullConstPtr::<u32>::try_from(ptr::null::<u32>()).is_err());
+
+  let mut value = 4u32;
+  let mut ptr = MutPtr::try_from_raw(ptr::addr_of_mut!(value)).unwrap();
+  *ptr.try_deref_mut().unwrap() += 1;
+  let view = ConstPtr::try_from_raw(ptr.raw().cast_const()).unwrap();
+  assert_eq!(view.try_deref().copied(), Ok(5));
+  assert_eq!(view.raw(), ptr::addr_of!(value));
+}
+
+#[test]
+fn checked_accessors_reject_null_and_invalidated_pointers() {
+  assert_eq!(ConstPtr::<u32>::default().checked_ref(), Err(PtrError::Null));
+  assert_eq!(MutPtr::<u32>::default().checked_mut(), Err(PtrError::Null));
+
+  let mut value = 4u32;
+  let mut ptr = MutPtr::new(&mut value);
+  *ptr.checked_mut().unwrap() += 1;
+  assert_eq!(ptr.checked_ref(), Ok(&5));
+
+  let stale = ptr;
+  ptr.invalidate();
+  assert_eq!(ptr.checked_ref(), Err(PtrError::Null));
+  if cfg!(all(feature = "checked", debug_assertions)) {
+    assert_eq!(stale.checked_ref(), Err(PtrError::Null));
+    assert_eq!(ConstPtr::from(stale).checked_ref(), Err(PtrError::Null));
+  }
+}
+
+#[test]
+#[should_panic(expected = "camera was never attached")]
+fn null_pointer_expect_panics_with_message() {
+  let ptr = ConstPtr::<u32>::default();
+  let _ = ptr.expect("camera was never attached");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "dereferenced a null MutPtr")]
+fn null_pointer_deref_panics_in_debug() {
+  let mut ptr = MutPtr::<u32>::default();
+  *ptr += 1;
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "dereferenced a null TaggedPtr")]
+fn null_tagged_pointer_deref_panics_in_debug() {
+  let ptr = TaggedPtr::<u32, 2>::new(MutPtr::default(), 1);
+  let _ = *ptr;
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "write through a null MutPtr")]
+fn null_pointer_write_panics_in_debug() {
+  MutPtr::<u32>::default().write(1);
+}
+
+#[test]
+fn present_pointer_expect_returns_value() {
+  let mut value = 3u32;
+  let mut ptr = MutPtr::new(&mut value);
+  *ptr.expect_mut("missing") += 1;
+  assert_eq!(*ptr.expect("missing"), 4);
+  assert_eq!(*ConstPtr::from(ptr).expect("missing"), 4);
+}
+
+#[test]
+fn pointers_compute_element_indexes() {
+  let mut particles = vec![0u64; 8];
+  let base = ConstPtr::from(&particles);
+  let fifth = ConstPtr::new(&particles[5]);
+  let first = ConstPtr::new(&particles[0]);
+
+  assert_eq!(unsafe { fifth.offset_from(first) }, 5);
+  assert_eq!(unsafe { first.offset_from(fifth) }, -5);
+  assert_eq!(fifth.element_index(base), Some(5));
+  assert_eq!(unsafe { fifth.add(3) }.element_index(base), None);
+  assert_eq!(fifth.cast::<u8>().wrapping_add(1).cast::<u64>().element_index(base), None);
+
+  let other = [0u64; 8];
+  assert_eq!(ConstPtr::new(&other[1]).element_index(base), None);
+
+  let slots = MutPtr::from(&mut particles);
+  let mut last = slots;
+  let last = last.get_mut(7).unwrap();
+  assert_eq!(last.element_index(slots), Some(7));
+  assert_eq!(unsafe { last.offset_from(slots.cast()) }, 7);
+}
+
+#[test]
+fn pointers_reject_misaligned_raw_pointers() {
+  #[repr(align(4))]
+  struct Packet([u8; 8]);
+
+  let packet = Packet([1, 0, 0, 0, 2, 0, 0, 0]);
+  let bytes = packet.0.as_ptr();
+  let misaligned = bytes.wrapping_add(1).cast::<u32>();
+
+  assert!(matches!(ConstPtr::try_from_raw(misaligned), Err(PtrError::Misaligned)));
+  assert!(!ConstPtr::from_raw_unchecked(misaligned).is_aligned());
+  assert_eq!(ConstPtr::from(bytes.wrapping_add(1)).align_offset(4), 3);
+  assert!(ConstPtr::try_from_raw(bytes.wrapping_add(1)).is_ok());
+
+  let aligned = ConstPtr::try_from_raw(bytes.cast::<u32>()).unwrap();
+  assert!(aligned.is_aligned());
+  assert_eq!(unsafe { aligned.add(1) }.read_volatile(), u32::from_ne_bytes([2, 0, 0, 0]));
+  assert!(matches!(MutPtr::<u32>::try_from_raw(ptr::null_mut()), Err(PtrError::Null)));
+
+  let words = [0u16; 4];
+  let raw: *const [u16] = ptr::slice_from_raw_parts(words.as_ptr().cast::<u8>().wrapping_add(1).cast::<u16>(), 2);
+  assert!(matches!(ConstPtr::try_from_raw(raw), Err(PtrError::Misaligned)));
+  assert_eq!(ConstPtr::try_from_raw(ptr::from_ref(&words[..])).unwrap().len(), 4);
+  assert!(ConstPtr::<str>::try_from_raw(ptr::from_ref("abc")).is_ok());
+
+  if cfg!(all(feature = "nightly", debug_assertions)) {
+    assert!(std::panic::catch_unwind(|| ConstPtr::from(misaligned)).is_err());
+  }
+}
+
+#[test]
+fn pointers_read_unaligned_values() {
+  let mut buffer = [0u8; 16];
+  buffer[1..5].copy_from_slice(&0xdead_beef_u32.to_le_bytes());
+  buffer[7..11].copy_from_slice(&0x0102_0304_u32.to_le_bytes());
+
+  let bytes = ConstPtr::from_slice(&buffer);
+  assert_eq!(u32::from_le(unsafe { bytes.read_unaligned_at::<u32>(1) }), 0xdead_beef);
+  assert_eq!(u32::from_le(unsafe { bytes.read_unaligned_at::<u32>(7) }), 0x0102_0304);
+  assert!(std::panic::catch_unwind(|| unsafe { bytes.read_unaligned_at::<u32>(13) }).is_err());
+
+  let field = ConstPtr::from_raw_unchecked(buffer.as_ptr().wrapping_add(1).cast::<u32>());
+  assert_eq!(u32::from_le(field.read_unaligned()), 0xdead_beef);
+
+  let mut field = MutPtr::from_raw_unchecked(buffer.as_mut_ptr().wrapping_add(3).cast::<u16>());
+  field.write_unaligned(0xabcd_u16.to_le());
+  assert_eq!(u16::from_le(field.read_unaligned()), 0xabcd);
+  assert_eq!(buffer[3..5], [0xcd, 0xab]);
+}
+
+#[test]
+fn slice_pointers_copy_elements() {
+  let source = vec![1u16, 2, 3, 4];
+  let mut target = vec![0u16; 4];
+  let mut ptr = MutPtr::from(&mut target);
+  ptr.copy_from_slice_ptr(ConstPtr::from(&source));
+  assert_eq!(target, source);
+
+  let mut expected = [1u8, 2, 3, 4, 5, 6];
+  let mut buffer = expected;
+  MutPtr::from_slice(&mut buffer).copy_within(0..4, 2);
+  expected.copy_within(0..4, 2);
+  assert_eq!(buffer, expected);
+
+  let short = [0u8; 2];
+  let mut ptr = MutPtr::from_slice(&mut buffer);
+  let result = std::panic::catch_unwind(move || ptr.copy_from_slice_ptr(ConstPtr::from_slice(&short)));
+  assert!(result.is_err());
+
+  let mut overlapping = [1u8, 2, 3, 4, 5, 6];
+  let base = overlapping.as_mut_ptr();
+  let mut dest = MutPtr::from(ptr::slice_from_raw_parts_mut(base.wrapping_add(2), 4));
+  dest.copy_from_slice_ptr(ConstPtr::from(ptr::slice_from_raw_parts(base.cast_const(), 4)));
+  assert_eq!(overlapping, expected);
+
+  let mut lanes = [0u32; 3];
+  let mut dst = MutPtr::from(lanes.as_mut_ptr());
+  let src = [7u32, 8, 9];
+  unsafe { dst.copy_from(ConstPtr::from(src.as_ptr()), 3) };
+  assert_eq!(lanes, src);
+}
+
+#[test]
+fn smart_pointer_counts_through_lifecycle() {
+  let asset = SmartPtr::new(String::from("terrain"));
+  let owner = asset.clone();
+  let observer = asset.downgrade();
+  let overlay = observer.clone();
+
+  assert_eq!(asset.strong_count(), 2);
+  assert_eq!(asset.weak_count(), 2);
+  assert_eq!(observer.strong_count(), 2);
+  assert_eq!(observer.weak_count(), 2);
+
+  drop(asset);
+  drop(owner);
+  assert_eq!(observer.strong_count(), 0);
+  assert_eq!(observer.weak_count(), 0);
+  assert!(observer.upgrade().is_none());
+
+  drop(observer);
+  assert_eq!(overlay.strong_count(), 0);
+  drop(overlay);
+
+  let empty = WeakPtr::<u8>::new();
+  assert_eq!((empty.strong_count(), empty.weak_count()), (0, 0));
+  assert_eq!(SmartPtr::<u8>::empty().weak_count(), 0);
+}
+
+#[test]
+fn smart_pointer_uniqueness() {
+  let mut asset = SmartPtr::new(vec![1u8]);
+  assert!(asset.is_unique());
+
+  let clone = asset.clone();
+  assert!(!asset.is_unique());
+  assert!(asset.get_mut().is_none());
+  drop(clone);
+
+  let weak = asset.downgrade();
+  assert!(!asset.is_unique());
+  asset.make_mut().push(2);
+  assert!(asset.is_unique());
+  assert!(weak.upgrade().is_none());
+  drop(weak);
+
+  asset.get_mut().unwrap().push(3);
+  assert_eq!(*asset, [1, 2, 3]);
+  assert!(!SmartPtr::<u8>::empty().is_unique());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn pointers_cast_pod_buffers() {
+  #[repr(C, align(4))]
+  struct Aligned([u8; 12]);
+
+  let mut buffer = Aligned([0; 12]);
+  buffer.0[..4].copy_from_slice(&7u32.to_ne_bytes());
+
+  let bytes = MutPtr::from_slice(&mut buffer.0);
+  let mut words = bytes.cast_slice_mut::<u32>().unwrap();
+  assert_eq!(words.len(), 3);
+  assert_eq!(words[0], 7);
+  words[2] = u32::MAX;
+
+  let back = ConstPtr::from(words).cast_slice::<u8>().unwrap();
+  assert_eq!(back.len(), 12);
+  assert_eq!(back.as_opt().unwrap()[8..], [0xff; 4]);
+
+  let misaligned = MutPtr::from_slice(&mut buffer.0[1..9]);
+  assert_eq!(misaligned.cast_slice_mut::<u32>().err(), Some(PtrError::Misaligned));
+  let odd = MutPtr::from_slice(&mut buffer.0[..6]);
+  assert_eq!(odd.cast_slice_mut::<u32>().err(), Some(PtrError::SizeMismatch));
+
+  let value = 0x0102_0304u32;
+  let pair = ConstPtr::new(&value).cast_pod::<[u16; 2]>().unwrap();
+  assert_eq!(pair.len(), 2);
+  assert_eq!(ConstPtr::new(&value).cast_pod::<u64>().err(), Some(PtrError::SizeMismatch));
+  assert_eq!(ConstPtr::<u32>::default().cast_pod::<f32>().err(), Some(PtrError::Null));
+}
+
+#[cfg(all(feature = "bytemuck", not(any(feature = "checked", feature = "borrow-checked"))))]
+#[test]
+fn pointers_wrap_raw_pointer_slices() {
+  use bytemuck::TransparentWrapper;
+
+  let values = [1u32, 2, 3];
+  let raw: Vec<*const u32> = values.iter().map(ptr::from_ref).collect();
+
+  let wrapped: &[ConstPtr<u32>] = ConstPtr::wrap_slice(&raw);
+  assert_eq!(wrapped.iter().map(|ptr| **ptr).collect::<Vec<_>>(), values);
+  assert_eq!(ConstPtr::peel_slice(wrapped), &raw[..]);
+
+  let mut value = 4u32;
+  let mut ptr = MutPtr::wrap(ptr::addr_of_mut!(value));
+  *ptr += 1;
+  assert_eq!(MutPtr::peel(ptr), ptr::addr_of_mut!(value));
+  assert_eq!(value, 5);
+}
+
+#[test]
+fn pointers_view_pointee_bytes() {
+  #[repr(C)]
+  struct Vertex {
+    position: [u16; 2],
+    color: u32,
+  }
+
+  let mut vertex = Vertex {
+    position: [1, 2],
+    color: 0xff00_00ff,
+  };
+
+  let view = unsafe { ConstPtr::new(&vertex).as_bytes() };
+  let manual = unsafe { std::slice::from_raw_parts(ptr::addr_of!(vertex).cast::<u8>(), mem::size_of::<Vertex>()) };
+  assert_eq!(view.as_opt().unwrap(), manual);
+
+  let mut bytes = unsafe { MutPtr::new(&mut vertex).as_bytes_mut() };
+  bytes.as_opt_mut().unwrap()[4..8].copy_from_slice(&0x1234_5678u32.to_ne_bytes());
+  assert_eq!(vertex.color, 0x1234_5678);
+  assert_eq!(vertex.position, [1, 2]);
+
+  let null = unsafe { ConstPtr::<Vertex>::default().as_bytes() };
+  assert!(null.null());
+  assert_eq!(null.len(), 0);
+}
+
+#[test]
+fn pointers_round_trip_through_c_user_data() {
+  struct Handler {
+    on_event: Box<dyn FnMut(i32)>,
+  }
+
+  // what a C library registering `callback` with `user_data` would end up doing
+  extern "C" fn callback(event: i32, user_data: *mut std::ffi::c_void) {
+    let mut handler = unsafe { MutPtr::<Handler>::from_void(user_data) };
+    (handler.on_event)(event);
+  }
+
+  fn fire(callback: extern "C" fn(i32, *mut std::ffi::c_void), user_data: *mut std::ffi::c_void) {
+    callback(1, user_data);
+    callback(2, user_data);
+  }
+
+  let seen = Rc::new(RefCell::new(Vec::new()));
+  let mut handler = Handler {
+    on_event: Box::new({
+      let seen = seen.clone();
+      move |event| seen.borrow_mut().push(event)
+    }),
+  };
+
+  fire(callback, MutPtr::new(&mut handler).into_void());
+  assert_eq!(*seen.borrow(), [1, 2]);
+
+  let value = 7;
+  let data = ConstPtr::new(&value).into_void();
+  assert_eq!(*unsafe { ConstPtr::<i32>::from_void(data) }, 7);
+
+  assert!(unsafe { ConstPtr::<i32>::from_void(ptr::null()) }.null());
+  assert!(unsafe { MutPtr::<Handler>::from_void(ptr::null_mut()) }.null());
+}
+
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+#[test]
+fn pointers_share_raw_pointer_layout() {
+  #[repr(C)]
+  struct Callbacks {
+    input: ConstPtr<u32>,
+    output: MutPtr<u32>,
+  }
+
+  #[repr(C)]
+  struct RawCallbacks {
+    input: *const u32,
+    output: *mut u32,
+  }
+
+  let input = 3;
+  let mut output = 0;
+  let raw = RawCallbacks {
+    input: &input,
+    output: &mut output,
+  };
+
+  let mut callbacks: Callbacks = unsafe { mem::transmute(raw) };
+  assert_eq!(callbacks.input.raw(), ptr::addr_of!(input));
+  *callbacks.output = *callbacks.input;
+  assert_eq!(output, 3);
+
+  let from_raw: ConstPtr<u32> = unsafe { mem::transmute(ptr::addr_of!(input)) };
+  assert_eq!(from_raw.addr(), ptr::addr_of!(input).addr());
+
+  let none: ConstPtr<u32> = unsafe { mem::transmute(None::<&u32>) };
+  assert!(none.null());
+  let some: ConstPtr<u32> = unsafe { mem::transmute(Some(&input)) };
+  assert_eq!(*some, 3);
+}
+
+#[test]
+fn array_pointers_unsize_to_slices() {
+  fn trace(matrix: ConstPtr<[f32]>) -> f32 {
+    (0..4).map(|i| matrix[i * 5]).sum()
+  }
+
+  let mut identity = [0.0f32; 16];
+  for i in 0..4 {
+    identity[i * 5] = 1.0;
+  }
+
+  let sized = ConstPtr::new(&identity);
+  assert_eq!(sized.as_slice_ptr().len(), 16);
+  assert_eq!(trace(sized.as_slice_ptr()), 4.0);
+
+  let unsized_ptr = ConstPtr::<[f32]>::from(&identity);
+  assert_eq!(unsized_ptr.len(), 16);
+  assert!(unsized_ptr.iter().eq(identity.iter()));
+  assert_eq!(trace(unsized_ptr), 4.0);
+
+  let mut elements = MutPtr::<[f32]>::from(&mut identity);
+  assert_eq!(elements.len(), 16);
+  elements[15] = 2.0;
+  assert_eq!(identity[15], 2.0);
+
+  let sized = MutPtr::new(&mut identity);
+  sized.as_slice_ptr()[0] = 3.0;
+  assert_eq!(trace(sized.as_slice_ptr().into()), 7.0);
+}
+
+#[test]
+fn generic_code_accepts_every_pointer() {
+  use crate::traits::{Ptr, PtrMut};
+
+  struct Node {
+    id: u32,
+  }
+
+  fn dump<P: Ptr<Pointee = Node>>(p: P) -> String {
+    if p.is_null() {
+      String::from("null")
+    } else {
+      format!("node {} at {:#x}", p.as_ref().id, p.raw_addr())
+    }
+  }
+
+  fn renumber<P: PtrMut<Pointee = Node>>(p: &mut P, id: u32) {
+    p.as_mut().id = id;
+  }
+
+  let mut node = Node { id: 1 };
+  let addr = ptr::addr_of!(node).addr();
+
+  assert_eq!(dump(ConstPtr::new(&node)), format!("node 1 at {addr:#x}"));
+  assert_eq!(dump(ConstPtr::<Node>::default()), "null");
+
+  let mut mut_ptr = MutPtr::new(&mut node);
+  renumber(&mut mut_ptr, 2);
+  assert_eq!(dump(mut_ptr), format!("node 2 at {addr:#x}"));
+
+  let mut smart = SmartPtr::new(Node { id: 3 });
+  let smart_addr = smart.as_const_ptr().addr();
+  renumber(&mut smart, 4);
+  assert_eq!(dump(smart.clone()), format!("node 4 at {smart_addr:#x}"));
+  assert_eq!(dump(SmartPtr::<Node>::empty()), "null");
+}
@@ -0,0 +1,137 @@
+//! One interface over `ConstPtr`, `MutPtr` and `SmartPtr`, for helpers that do not care which pointer they are given
+//!
+//! `Ptr::as_ref` and `PtrMut::as_mut` share their names with `AsRef` and `AsMut`, so this module is kept out of the
+//! crate root and should only be imported where the generic code lives.
+
+#[cfg(feature = "alloc")]
+use super::SmartPtr;
+use super::{ConstPtr, MutPtr};
+
+mod sealed {
+  pub trait Sealed {}
+
+  pub trait SealedAlign {}
+}
+
+/// Read access shared by every pointer in the crate, sealed so it can only be implemented here
+pub trait Ptr: sealed::Sealed {
+  type Pointee: ?Sized;
+
+  /// The address without its provenance
+  fn raw_addr(&self) -> usize;
+
+  fn is_null(&self) -> bool;
+
+  /// Same contract as dereferencing the pointer, a null pointer trips a debug assertion or panics
+  fn as_ref(&self) -> &Self::Pointee;
+}
+
+/// Mutable access for the pointers that allow it
+pub trait PtrMut: Ptr {
+  fn as_mut(&mut self) -> &mut Self::Pointee;
+}
+
+impl<T: ?Sized> sealed::Sealed for ConstPtr<T> {}
+
+impl<T: ?Sized> Ptr for ConstPtr<T> {
+  type Pointee = T;
+
+  fn raw_addr(&self) -> usize {
+    self.addr()
+  }
+
+  fn is_null(&self) -> bool {
+    self.null()
+  }
+
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn as_ref(&self) -> &T {
+    AsRef::as_ref(self)
+  }
+}
+
+impl<T: ?Sized> sealed::Sealed for MutPtr<T> {}
+
+impl<T: ?Sized> Ptr for MutPtr<T> {
+  type Pointee = T;
+
+  fn raw_addr(&self) -> usize {
+    self.addr()
+  }
+
+  fn is_null(&self) -> bool {
+    self.null()
+  }
+
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn as_ref(&self) -> &T {
+    AsRef::as_ref(self)
+  }
+}
+
+impl<T: ?Sized> PtrMut for MutPtr<T> {
+  #[cfg_attr(any(feature = "checked", feature = "borrow-checked", debug_assertions), track_caller)]
+  fn as_mut(&mut self) -> &mut T {
+    AsMut::as_mut(self)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> sealed::Sealed for SmartPtr<T> {}
+
+/// Goes through the value pointer, an empty `SmartPtr` counts as null
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Ptr for SmartPtr<T> {
+  type Pointee = T;
+
+  fn raw_addr(&self) -> usize {
+    self.as_const_ptr().addr()
+  }
+
+  fn is_null(&self) -> bool {
+    !self.valid()
+  }
+
+  fn as_ref(&self) -> &T {
+    self.access()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> PtrMut for SmartPtr<T> {
+  fn as_mut(&mut self) -> &mut T {
+    self.access_mut()
+  }
+}
+
+/// Pointees whose alignment can be checked from a raw pointer alone: sized types, slices and `str`.
+///
+/// A `dyn Trait` keeps its alignment in the vtable, so `try_from_raw` is not available for it and `From` is the way in.
+pub trait RawAlign: sealed::SealedAlign {
+  /// Null counts as aligned
+  fn is_aligned_raw(ptr: *const Self) -> bool;
+}
+
+impl<T> sealed::SealedAlign for T {}
+
+impl<T> RawAlign for T {
+  fn is_aligned_raw(ptr: *const Self) -> bool {
+    ptr.is_aligned()
+  }
+}
+
+impl<T> sealed::SealedAlign for [T] {}
+
+impl<T> RawAlign for [T] {
+  fn is_aligned_raw(ptr: *const Self) -> bool {
+    ptr.cast::<T>().is_aligned()
+  }
+}
+
+impl sealed::SealedAlign for str {}
+
+impl RawAlign for str {
+  fn is_aligned_raw(_ptr: *const Self) -> bool {
+    true
+  }
+}
@@ -0,0 +1,23 @@
+//! Implicit unsizing coercions for the `nightly` feature, so `MutPtr<Concrete>` converts to `MutPtr<dyn Trait>`
+//! the way `Box` and `Rc` do
+//!
+//! `DispatchFromDyn` needs every field but the pointer to be zero sized, which `Meta` only is without the `checked` and
+//! `borrow-checked` bookkeeping, and never holds for `SmartPtr` with its separate reference count.
+
+use super::{ConstPtr, MutPtr};
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+use core::ops::DispatchFromDyn;
+use core::{marker::Unsize, ops::CoerceUnsized};
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<ConstPtr<U>> for ConstPtr<T> {}
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<MutPtr<U>> for MutPtr<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<super::SmartPtr<U>> for super::SmartPtr<T> {}
+
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<ConstPtr<U>> for ConstPtr<T> {}
+
+#[cfg(not(any(feature = "checked", feature = "borrow-checked")))]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<MutPtr<U>> for MutPtr<T> {}
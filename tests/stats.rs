@@ -0,0 +1,28 @@
+// a binary of its own, so no other test allocates while the counters are compared
+#![cfg(feature = "stats")]
+
+use ptr::{stats, SmartPtr};
+
+#[test]
+fn live_allocations_return_to_baseline() {
+  let live = stats::live();
+  let allocated = stats::allocated();
+  stats::reset_peak();
+
+  let entities = (0..16).map(SmartPtr::new).collect::<Vec<_>>();
+  let clones = entities.iter().step_by(2).cloned().collect::<Vec<_>>();
+  assert_eq!(stats::live(), live + 16);
+  assert_eq!(stats::allocated(), allocated + 16);
+
+  drop(entities);
+  assert_eq!(stats::live(), live + 8);
+  let weak = clones[0].downgrade();
+  drop(clones);
+  assert_eq!(stats::live(), live);
+  drop(weak);
+
+  assert_eq!(SmartPtr::new(5).into_inner(), Some(5));
+  assert_eq!(stats::live(), live);
+  assert_eq!(stats::peak(), live + 16);
+  assert_eq!(stats::allocated(), allocated + 17);
+}
@@ -0,0 +1,7 @@
+use ptr::MutPtr;
+
+fn main() {
+  let mut name = String::from("packet");
+  let copy = MutPtr::new(&mut name).read_unaligned();
+  drop(copy);
+}
@@ -0,0 +1,7 @@
+use ptr::ConstPtr;
+
+fn main() {
+  let name = String::from("device");
+  let copy = ConstPtr::new(&name).read_volatile();
+  drop(copy);
+}
@@ -0,0 +1,8 @@
+use ptr::RefMutPtr;
+
+fn main() {
+  let mut value = 5u32;
+  let mut ptr = RefMutPtr::new(&mut value);
+  assert_eq!(value, 5);
+  *ptr += 1;
+}
@@ -0,0 +1,10 @@
+use ptr::RefPtr;
+
+fn main() {
+  let ptr;
+  {
+    let value = 5u32;
+    ptr = RefPtr::new(&value);
+  }
+  assert_eq!(*ptr, 5);
+}
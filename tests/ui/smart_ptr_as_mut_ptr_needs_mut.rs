@@ -0,0 +1,7 @@
+use ptr::SmartPtr;
+
+fn main() {
+  let ptr = SmartPtr::new(1u32);
+  let shared = &ptr;
+  let _view = shared.as_mut_ptr();
+}